@@ -0,0 +1,113 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::MediaPlayer;
+use crate::TrackDescription;
+use crate::enums::AudioChannel;
+use crate::tools::from_cstr;
+
+pub trait MediaPlayerAudioEx {
+    fn toggle_mute(&self);
+    fn get_mute(&self) -> Option<bool>;
+    fn set_mute(&self, muted: bool);
+    /// Volume in percent, 100 is the nominal volume. Returns None if there is no audio output.
+    fn get_volume(&self) -> Option<i32>;
+    fn set_volume(&self, volume: i32) -> Result<(), ()>;
+    fn get_audio_track_count(&self) -> Option<i32>;
+    fn get_audio_track_description(&self) -> Option<Vec<TrackDescription>>;
+    fn get_audio_track(&self) -> Option<i32>;
+    fn set_audio_track(&self, track: i32) -> Result<(), ()>;
+    fn get_channel(&self) -> Option<AudioChannel>;
+    fn set_channel(&self, channel: AudioChannel) -> Result<(), ()>;
+    /// Audio delay in microseconds, positive values delay the audio.
+    fn get_audio_delay(&self) -> i64;
+    fn set_audio_delay(&self, delay: i64) -> Result<(), ()>;
+}
+
+impl MediaPlayerAudioEx for MediaPlayer {
+    fn toggle_mute(&self) {
+        unsafe{ (sys::get_vlc_dll().libvlc_audio_toggle_mute)(self.ptr); }
+    }
+    fn get_mute(&self) -> Option<bool> {
+        let r = unsafe{ (sys::get_vlc_dll().libvlc_audio_get_mute)(self.ptr) };
+        if r == -1 { None }else{ Some(r != 0) }
+    }
+    fn set_mute(&self, muted: bool) {
+        unsafe{ (sys::get_vlc_dll().libvlc_audio_set_mute)(self.ptr, if muted { 1 }else{ 0 }); }
+    }
+    fn get_volume(&self) -> Option<i32> {
+        let volume = unsafe{ (sys::get_vlc_dll().libvlc_audio_get_volume)(self.ptr) };
+        if volume == -1 { None }else{ Some(volume) }
+    }
+    fn set_volume(&self, volume: i32) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_set_volume)(self.ptr, volume) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn get_audio_track_count(&self) -> Option<i32> {
+        let count = unsafe{ (sys::get_vlc_dll().libvlc_audio_get_track_count)(self.ptr) };
+        if count == -1 { None }else{ Some(count) }
+    }
+    fn get_audio_track_description(&self) -> Option<Vec<TrackDescription>> {
+        unsafe{ TrackDescription::from_list((sys::get_vlc_dll().libvlc_audio_get_track_description)(self.ptr)) }
+    }
+    fn get_audio_track(&self) -> Option<i32> {
+        let track = unsafe{ (sys::get_vlc_dll().libvlc_audio_get_track)(self.ptr) };
+        if track == -1 { None }else{ Some(track) }
+    }
+    fn set_audio_track(&self, track: i32) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_set_track)(self.ptr, track) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn get_channel(&self) -> Option<AudioChannel> {
+        AudioChannel::from_raw(unsafe{ (sys::get_vlc_dll().libvlc_audio_get_channel)(self.ptr) })
+    }
+    fn set_channel(&self, channel: AudioChannel) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_set_channel)(self.ptr, channel as i32) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn get_audio_delay(&self) -> i64 {
+        unsafe{ (sys::get_vlc_dll().libvlc_audio_get_delay)(self.ptr) }
+    }
+    fn set_audio_delay(&self, delay: i64) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_set_delay)(self.ptr, delay) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+}
+
+/// Description of an audio output module.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AudioOutput {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Description of an audio output device.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AudioOutputDevice {
+    /// Device identifier, to be passed to `MediaPlayer::set_audio_device()`
+    pub id: String,
+    pub description: Option<String>,
+}
+
+impl AudioOutputDevice {
+    // Takes ownership of the list and releases it
+    pub(crate) unsafe fn from_list(p0: *mut sys::libvlc_audio_output_device_t) -> Vec<AudioOutputDevice> {
+        let mut devices = Vec::new();
+        let mut p = p0;
+
+        while !p.is_null() {
+            if let Some(id) = from_cstr((*p).psz_device) {
+                devices.push(AudioOutputDevice{ id, description: from_cstr((*p).psz_description) });
+            }
+            p = (*p).p_next;
+        }
+        if !p0.is_null() { (sys::get_vlc_dll().libvlc_audio_output_device_list_release)(p0); }
+        devices
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys::c_bool;
+use libc::{c_void, c_char, c_int, c_uint, c_float};
+use std::sync::{Arc, Mutex};
+
+/// Sample format of the decoded audio, in native endianness.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SampleFormat {
+    /// Signed 16-bit integer
+    S16N,
+    /// Signed 32-bit integer
+    S32N,
+    /// 32-bit float
+    FL32,
+}
+
+impl SampleFormat {
+    fn fourcc(&self) -> &'static [u8; 4] {
+        match *self {
+            SampleFormat::S16N => b"S16N",
+            SampleFormat::S32N => b"S32N",
+            SampleFormat::FL32 => b"FL32",
+        }
+    }
+}
+
+/// Format of the samples delivered to an `AudioSink`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AudioFormat {
+    pub format: SampleFormat,
+    /// Sample rate in Hz
+    pub rate: u32,
+    pub channels: u32,
+}
+
+/// Interleaved samples, `frames * channels` values.
+#[derive(Clone, Copy, Debug)]
+pub enum Samples<'a> {
+    S16(&'a [i16]),
+    S32(&'a [i32]),
+    F32(&'a [f32]),
+}
+
+impl<'a> Samples<'a> {
+    /// Number of values (all channels).
+    pub fn len(&self) -> usize {
+        match *self {
+            Samples::S16(s) => s.len(),
+            Samples::S32(s) => s.len(),
+            Samples::F32(s) => s.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Receives the decoded audio of a `MediaPlayer`, see `MediaPlayer::set_audio_sink()`.
+///
+/// All methods are called from libvlc threads. Timestamps are in the libvlc clock
+/// (in µs, see `sys::libvlc_delay()`).
+pub trait AudioSink: Send + 'static {
+    /// Choose the sample format for an audio stream with the given rate and channel count,
+    /// libvlc converts the audio if necessary. Return None to refuse the audio.
+    fn format(&mut self, rate: u32, channels: u32) -> Option<AudioFormat>;
+
+    /// Play the samples, `pts` is the time the first sample should be played at.
+    fn play(&mut self, samples: Samples<'_>, pts: i64);
+
+    /// Pause playback at `pts`.
+    fn pause(&mut self, _pts: i64) {}
+
+    /// Resume playback at `pts`.
+    fn resume(&mut self, _pts: i64) {}
+
+    /// Discard all pending samples.
+    fn flush(&mut self, _pts: i64) {}
+
+    /// Wait until all pending samples have been played.
+    fn drain(&mut self) {}
+
+    /// Return true to apply the volume and mute state in `set_volume()` instead of libvlc.
+    ///
+    /// By default libvlc scales the samples in software before `play()`. A sink taking over the
+    /// volume receives unscaled samples, and the player volume has no effect unless it applies it.
+    /// Queried once, when the sink is set.
+    fn handles_volume(&self) -> bool { false }
+
+    /// The volume or mute state of the player changed, only called if `handles_volume()` returns true.
+    /// `volume` is linear, 1.0 being the nominal volume.
+    fn set_volume(&mut self, _volume: f32, _muted: bool) {}
+
+    /// The audio output was closed.
+    fn cleanup(&mut self) {}
+}
+
+pub(crate) type SharedAudioHandler = Arc<Mutex<AudioHandler>>;
+
+// Player level slot, owned by the MediaPlayer and passed as opaque pointer to libvlc.
+// Each audio output takes its own reference to the handler in the setup callback,
+// so the handler can be replaced or removed while an output is running.
+pub(crate) type AudioHandlerSlot = Mutex<Option<SharedAudioHandler>>;
+
+pub(crate) enum AudioHandler {
+    Sink(Box<dyn AudioSink>),
+    Callbacks(AudioCallbacks),
+}
+
+// For MediaPlayer::set_callbacks
+pub(crate) struct AudioCallbacks {
+    pub play: Box<dyn Fn(*const c_void, u32, i64) + Send + 'static>,
+    pub pause: Option<Box<dyn Fn(i64) + Send + 'static>>,
+    pub resume: Option<Box<dyn Fn(i64) + Send + 'static>>,
+    pub flush: Option<Box<dyn Fn(i64) + Send + 'static>>,
+    pub drain: Option<Box<dyn Fn() + Send + 'static>>,
+}
+
+// State of one audio output, created in the setup callback and freed in the cleanup callback
+struct AudioOutput {
+    handler: SharedAudioHandler,
+    format: AudioFormat,
+}
+
+impl AudioOutput {
+    unsafe fn with_handler<F: FnOnce(&mut AudioHandler)>(opaque: *mut c_void, f: F) {
+        let output = &*(opaque as *const AudioOutput);
+        if let Ok(mut handler) = output.handler.lock() {
+            f(&mut handler);
+        }
+    }
+}
+
+pub(crate) unsafe extern "C" fn audio_setup(
+    opaque: *mut *mut c_void, format: *mut c_char, rate: *mut c_uint, channels: *mut c_uint) -> c_int
+{
+    let slot = &*(*opaque as *const AudioHandlerSlot);
+    let handler = match slot.lock().ok().and_then(|s| s.clone()) {
+        Some(h) => h,
+        None => { return -1; },
+    };
+
+    let negotiated = match handler.lock() {
+        Ok(mut h) => match *h {
+            AudioHandler::Sink(ref mut sink) => match sink.format(*rate, *channels) {
+                Some(f) if f.rate > 0 && f.channels > 0 => f,
+                _ => { return -1; },
+            },
+            // The format amem uses without a setup callback, which `set_callbacks()` users expect
+            AudioHandler::Callbacks(_) => AudioFormat{ format: SampleFormat::S16N, rate: 44100, channels: 2 },
+        },
+        Err(_) => { return -1; },
+    };
+
+    for (i, c) in negotiated.format.fourcc().iter().enumerate() {
+        *format.add(i) = *c as c_char;
+    }
+    *rate = negotiated.rate;
+    *channels = negotiated.channels;
+
+    *opaque = Box::into_raw(Box::new(AudioOutput{ handler, format: negotiated })) as *mut c_void;
+    0
+}
+
+pub(crate) unsafe extern "C" fn audio_cleanup(opaque: *mut c_void) {
+    let output = Box::from_raw(opaque as *mut AudioOutput);
+    if let Ok(mut handler) = output.handler.lock() {
+        if let AudioHandler::Sink(ref mut sink) = *handler {
+            sink.cleanup();
+        }
+    };
+}
+
+pub(crate) unsafe extern "C" fn audio_play(opaque: *mut c_void, samples: *const c_void, count: c_uint, pts: i64) {
+    let format = (*(opaque as *const AudioOutput)).format;
+    AudioOutput::with_handler(opaque, |handler| match *handler {
+        AudioHandler::Sink(ref mut sink) => {
+            let len = count as usize * format.channels as usize;
+            let samples = match format.format {
+                SampleFormat::S16N => Samples::S16(::std::slice::from_raw_parts(samples as *const i16, len)),
+                SampleFormat::S32N => Samples::S32(::std::slice::from_raw_parts(samples as *const i32, len)),
+                SampleFormat::FL32 => Samples::F32(::std::slice::from_raw_parts(samples as *const f32, len)),
+            };
+            sink.play(samples, pts);
+        },
+        AudioHandler::Callbacks(ref cb) => (cb.play)(samples, count, pts),
+    });
+}
+
+pub(crate) unsafe extern "C" fn audio_pause(opaque: *mut c_void, pts: i64) {
+    AudioOutput::with_handler(opaque, |handler| match *handler {
+        AudioHandler::Sink(ref mut sink) => sink.pause(pts),
+        AudioHandler::Callbacks(ref cb) => if let Some(ref f) = cb.pause { f(pts) },
+    });
+}
+
+pub(crate) unsafe extern "C" fn audio_resume(opaque: *mut c_void, pts: i64) {
+    AudioOutput::with_handler(opaque, |handler| match *handler {
+        AudioHandler::Sink(ref mut sink) => sink.resume(pts),
+        AudioHandler::Callbacks(ref cb) => if let Some(ref f) = cb.resume { f(pts) },
+    });
+}
+
+pub(crate) unsafe extern "C" fn audio_flush(opaque: *mut c_void, pts: i64) {
+    AudioOutput::with_handler(opaque, |handler| match *handler {
+        AudioHandler::Sink(ref mut sink) => sink.flush(pts),
+        AudioHandler::Callbacks(ref cb) => if let Some(ref f) = cb.flush { f(pts) },
+    });
+}
+
+pub(crate) unsafe extern "C" fn audio_drain(opaque: *mut c_void) {
+    AudioOutput::with_handler(opaque, |handler| match *handler {
+        AudioHandler::Sink(ref mut sink) => sink.drain(),
+        AudioHandler::Callbacks(ref cb) => if let Some(ref f) = cb.drain { f() },
+    });
+}
+
+pub(crate) unsafe extern "C" fn audio_set_volume(opaque: *mut c_void, volume: c_float, mute: c_bool) {
+    AudioOutput::with_handler(opaque, |handler| {
+        if let AudioHandler::Sink(ref mut sink) = *handler {
+            sink.set_volume(volume, mute != 0);
+        }
+    });
+}
@@ -7,10 +7,15 @@ use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::ffi::CString;
 use std::i32;
+use std::any::Any;
+use std::sync::{Arc, Mutex, Condvar};
+use std::time::{Duration, Instant};
+use std::path::PathBuf;
 use libc::{c_void, c_char};
 use crate::sys;
-use crate::tools::{to_cstr, from_cstr, from_cstr_ref};
+use crate::tools::{to_cstr, from_cstr, from_cstr_ref, from_cstr_path};
 use crate::enums::*;
+use crate::audio::{AudioOutput, AudioOutputDevice};
 
 /// Retrieve libvlc version.
 pub fn version() -> String {
@@ -119,6 +124,33 @@ impl Instance {
         }
     }
 
+    /// Returns the audio output modules that are available.
+    pub fn audio_outputs(&self) -> Vec<AudioOutput> {
+        unsafe{
+            let p0 = (sys::get_vlc_dll().libvlc_audio_output_list_get)(self.ptr);
+            let mut outputs = Vec::new();
+            let mut p = p0;
+
+            while !p.is_null() {
+                if let Some(name) = from_cstr((*p).psz_name) {
+                    outputs.push(AudioOutput{ name, description: from_cstr((*p).psz_description) });
+                }
+                p = (*p).p_next;
+            }
+            if !p0.is_null() { (sys::get_vlc_dll().libvlc_audio_output_list_release)(p0); }
+            outputs
+        }
+    }
+
+    /// Returns the devices of the audio output module `output`.
+    /// Prefer `MediaPlayer::audio_devices()`, not all modules can list their devices this way.
+    pub fn audio_output_devices(&self, output: &str) -> Vec<AudioOutputDevice> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_audio_output_device_list_get)(self.ptr, to_cstr(output).as_ptr());
+            AudioOutputDevice::from_list(p)
+        }
+    }
+
     /// Returns the VLM event manager
     pub fn vlm_event_manager<'a>(&'a self) -> EventManager<'a> {
         unsafe{
@@ -238,6 +270,7 @@ pub enum Event {
     MediaMetaChanged(Meta),
     MediaSubItemAdded,
     MediaDurationChanged(i64),
+    /// Raw `libvlc_media_parsed_status_t`, see `ParsedStatus::from_raw()`
     MediaParsedChanged(i32),
     MediaFreed,
     MediaStateChanged(State),
@@ -259,7 +292,8 @@ pub enum Event {
     MediaPlayerSeekableChanged,
     MediaPlayerPausableChanged,
     MediaPlayerTitleChanged,
-    MediaPlayerSnapshotTaken,
+    /// Path of the written file
+    MediaPlayerSnapshotTaken(Option<PathBuf>),
     MediaPlayerLengthChanged,
     MediaPlayerVout,
     MediaPlayerScrambledChanged,
@@ -332,6 +366,73 @@ unsafe extern "C" fn event_manager_callback(pe: *const sys::libvlc_event_t, data
     f(conv_event(pe), VLCObject{ ptr: (*pe).p_obj });
 }
 
+// Attach to `events` of `em`, run `start` and block until `done` returns true. `done` is
+// checked again every time one of the events passes `filter`, and at least every
+// EVENT_POLL_INTERVAL in case an event was missed, until the timeout expires.
+// Returns false immediately if `start` fails. `filter` is called from libvlc threads.
+pub(crate) unsafe fn wait_for_events<F, S, D>(
+    em: *mut sys::libvlc_event_manager_t, events: &[EventType], timeout: Option<Duration>,
+    filter: F, start: S, done: D) -> bool
+    where F: Fn(&sys::libvlc_event_t) -> bool + Sync, S: FnOnce() -> bool, D: Fn() -> bool
+{
+    let waiter = EventWaiter{ fired: Mutex::new(false), cond: Condvar::new(), filter };
+    let data = &waiter as *const EventWaiter<F> as *mut c_void;
+
+    for event in events {
+        (sys::get_vlc_dll().libvlc_event_attach)(em, *event as i32, event_waiter_cb::<F>, data);
+    }
+
+    let started = start();
+
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let mut result = started && done();
+    while started && !result {
+        let mut fired = waiter.fired.lock().unwrap_or_else(|e| e.into_inner());
+        if !*fired {
+            let mut wait = EVENT_POLL_INTERVAL;
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                wait = wait.min(deadline - now);
+            }
+            fired = waiter.cond.wait_timeout(fired, wait).unwrap_or_else(|e| e.into_inner()).0;
+        }
+        *fired = false;
+        drop(fired);
+        result = done();
+    }
+
+    // libvlc holds the event manager lock while invoking callbacks, so
+    // after detaching, the waiter is no longer referenced
+    for event in events {
+        (sys::get_vlc_dll().libvlc_event_detach)(em, *event as i32, event_waiter_cb::<F>, data);
+    }
+
+    result
+}
+
+// For wait_for_events
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+struct EventWaiter<F> {
+    fired: Mutex<bool>,
+    cond: Condvar,
+    filter: F,
+}
+
+unsafe extern "C" fn event_waiter_cb<F: Fn(&sys::libvlc_event_t) -> bool>(pe: *const sys::libvlc_event_t, data: *mut c_void) {
+    let waiter = &*(data as *const EventWaiter<F>);
+    if !(waiter.filter)(&*pe) {
+        return;
+    }
+    if let Ok(mut fired) = waiter.fired.lock() {
+        *fired = true;
+    }
+    waiter.cond.notify_all();
+}
+
 // Convert c-style libvlc_event_t to Event
 fn conv_event(pe: *const sys::libvlc_event_t) -> Event {
     let event_type: EventType = unsafe{ ::std::mem::transmute((*pe)._type) };
@@ -419,7 +520,9 @@ fn conv_event(pe: *const sys::libvlc_event_t) -> Event {
             Event::MediaPlayerTitleChanged
         },
         EventType::MediaPlayerSnapshotTaken => {
-            Event::MediaPlayerSnapshotTaken
+            unsafe{
+                Event::MediaPlayerSnapshotTaken(from_cstr_path((*pe).u.media_player_snapshot_taken.psz_filename))
+            }
         },
         EventType::MediaPlayerLengthChanged => {
             Event::MediaPlayerLengthChanged
@@ -532,6 +635,16 @@ pub struct VLCObject {
 }
 
 impl VLCObject {
+    /// Get the value attached with `Media::set_data()` to the media that emitted the event.
+    ///
+    /// # Safety
+    ///
+    /// The object must be a media, i.e. the event must be one of the `Media*` events
+    /// emitted by a `Media` event manager.
+    pub unsafe fn media_data<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        crate::media::media_data(self.ptr as *mut sys::libvlc_media_t)
+    }
+
     /// Returns raw pointer
     pub fn raw(&self) -> *mut c_void {
         self.ptr
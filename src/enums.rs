@@ -32,7 +32,10 @@ pub enum Meta {
     Season,
     Episode,
     ShowName,
-    Actors
+    Actors,
+    AlbumArtist,
+    DiscNumber,
+    DiscTotal
 }
 
 #[repr(C)]
@@ -57,6 +60,81 @@ pub enum TrackType {
     Text    = 2
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MediaType {
+    Unknown = 0,
+    File,
+    Directory,
+    Disc,
+    Stream,
+    Playlist,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SlaveKind {
+    Subtitle = 0,
+    Audio,
+}
+
+/// Orientation of a video track, named after the position of the first row / column.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Orientation {
+    /// Normal. Top line represents top, left column left
+    TopLeft = 0,
+    /// Flipped horizontally
+    TopRight,
+    /// Flipped vertically
+    BottomLeft,
+    /// Rotated 180 degrees
+    BottomRight,
+    /// Transposed
+    LeftTop,
+    /// Rotated 90 degrees clockwise (or 270 anti-clockwise)
+    LeftBottom,
+    /// Rotated 90 degrees anti-clockwise
+    RightTop,
+    /// Anti-transposed
+    RightBottom,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Projection {
+    Rectangular = 0,
+    /// 360 spherical
+    Equirectangular = 1,
+    CubemapLayoutStandard = 0x100,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AudioChannel {
+    Stereo = 1,
+    /// Left and right channels swapped
+    RStereo,
+    Left,
+    Right,
+    /// Dolby Surround
+    Dolbys,
+}
+
+impl AudioChannel {
+    /// Convert a raw libvlc_audio_output_channel_t, returns None on error.
+    pub(crate) fn from_raw(channel: i32) -> Option<AudioChannel> {
+        match channel {
+            1 => Some(AudioChannel::Stereo),
+            2 => Some(AudioChannel::RStereo),
+            3 => Some(AudioChannel::Left),
+            4 => Some(AudioChannel::Right),
+            5 => Some(AudioChannel::Dolbys),
+            _ => None,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Position {
@@ -72,6 +150,17 @@ pub enum Position {
     BottomRight,
 }
 
+/// Teletext navigation keys, see `MediaPlayer::press_teletext_key()`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TeletextKey {
+    Red    = (b'r' as isize) << 16,
+    Green  = (b'g' as isize) << 16,
+    Yellow = (b'y' as isize) << 16,
+    Blue   = (b'b' as isize) << 16,
+    Index  = (b'i' as isize) << 16,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub enum VideoAdjustOption {
@@ -83,14 +172,61 @@ pub enum VideoAdjustOption {
     Gamma
 }
 
-// #[repr(C)]
-// #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-// pub enum ParseFlag {
-//     ParseLocal,
-//     ParseNetwork,
-//     FetchLocal,
-//     FetchNetwork,
-// }
+/// Flags for `Media::parse_with()`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ParseFlags {
+    /// Parse media if it's a local file (always done, kept for clarity)
+    pub local: bool,
+    /// Parse media even if it's a network file
+    pub network: bool,
+    /// Fetch meta and covert art using local resources
+    pub fetch_local: bool,
+    /// Fetch meta and covert art using network resources
+    pub fetch_network: bool,
+    /// Interact with the user (e.g. for credentials) when preparsing this item
+    pub interact: bool,
+}
+
+impl Default for ParseFlags {
+    fn default() -> ParseFlags {
+        ParseFlags { local: true, network: false, fetch_local: false, fetch_network: false, interact: false }
+    }
+}
+
+impl ParseFlags {
+    pub(crate) fn bits(&self) -> i32 {
+        use crate::sys::*;
+        let mut bits = libvlc_media_parse_local;
+        if self.network { bits |= libvlc_media_parse_network; }
+        if self.fetch_local { bits |= libvlc_media_fetch_local; }
+        if self.fetch_network { bits |= libvlc_media_fetch_network; }
+        if self.interact { bits |= libvlc_media_do_interact; }
+        bits
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ParsedStatus {
+    Skipped = 1,
+    Failed,
+    Timeout,
+    Done,
+}
+
+impl ParsedStatus {
+    /// Convert a raw libvlc_media_parsed_status_t, e.g. of `Event::MediaParsedChanged`.
+    /// Returns None if the media was not parsed yet or the status is unknown.
+    pub fn from_raw(status: i32) -> Option<ParsedStatus> {
+        match status {
+            1 => Some(ParsedStatus::Skipped),
+            2 => Some(ParsedStatus::Failed),
+            3 => Some(ParsedStatus::Timeout),
+            4 => Some(ParsedStatus::Done),
+            _ => None,
+        }
+    }
+}
 
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -0,0 +1,236 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::tools::from_cstr;
+use std::fmt;
+use std::str::FromStr;
+
+/// Lowest amplification (in dB) accepted for the preamp and the bands.
+pub const EQUALIZER_MIN_AMP: f32 = -20.0;
+/// Highest amplification (in dB) accepted for the preamp and the bands.
+pub const EQUALIZER_MAX_AMP: f32 = 20.0;
+
+fn valid_amp(db: f32) -> bool {
+    (EQUALIZER_MIN_AMP..=EQUALIZER_MAX_AMP).contains(&db)
+}
+
+/// Audio equalizer settings, applied with `MediaPlayer::set_equalizer()`.
+pub struct Equalizer {
+    pub(crate) ptr: *mut sys::libvlc_equalizer_t,
+}
+
+unsafe impl Send for Equalizer {}
+
+impl Equalizer {
+    /// Create a flat equalizer, with all bands and the preamp at 0 dB.
+    pub fn new() -> Option<Equalizer> {
+        let p = unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_new)() };
+        if p.is_null() { None }else{ Some(Equalizer{ptr: p}) }
+    }
+
+    /// Create an equalizer initialized from the preset at index `preset`, see `presets()`.
+    pub fn from_preset(preset: u32) -> Option<Equalizer> {
+        let p = unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_new_from_preset)(preset) };
+        if p.is_null() { None }else{ Some(Equalizer{ptr: p}) }
+    }
+
+    /// Create an equalizer from saved settings.
+    pub fn from_settings(settings: &EqualizerSettings) -> Result<Equalizer, InvalidEqualizerSettings> {
+        settings.validate()?;
+        let eq = Equalizer::new().ok_or(InvalidEqualizerSettings{ reason: "could not create the equalizer" })?;
+        unsafe{
+            (sys::get_vlc_dll().libvlc_audio_equalizer_set_preamp)(eq.ptr, settings.preamp);
+            for (band, &db) in settings.amps.iter().enumerate() {
+                (sys::get_vlc_dll().libvlc_audio_equalizer_set_amp_at_index)(eq.ptr, db, band as u32);
+            }
+        }
+        Ok(eq)
+    }
+
+    /// Names of the built-in presets, the index of a name is the index to pass to `from_preset()`.
+    pub fn presets() -> Vec<String> {
+        unsafe{
+            let count = (sys::get_vlc_dll().libvlc_audio_equalizer_get_preset_count)();
+            (0..count).map(|i| {
+                from_cstr((sys::get_vlc_dll().libvlc_audio_equalizer_get_preset_name)(i)).unwrap_or_default()
+            }).collect()
+        }
+    }
+
+    /// Center frequencies (in Hz) of the bands, the index of a frequency is the band index.
+    pub fn bands() -> Vec<f32> {
+        unsafe{
+            let count = (sys::get_vlc_dll().libvlc_audio_equalizer_get_band_count)();
+            (0..count).map(|i| (sys::get_vlc_dll().libvlc_audio_equalizer_get_band_frequency)(i)).collect()
+        }
+    }
+
+    /// Get the preamp value (in dB).
+    pub fn preamp(&self) -> f32 {
+        unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_get_preamp)(self.ptr) }
+    }
+
+    /// Set the preamp value (in dB), between `EQUALIZER_MIN_AMP` and `EQUALIZER_MAX_AMP`.
+    pub fn set_preamp(&mut self, db: f32) -> Result<(), InvalidEqualizerSettings> {
+        if !valid_amp(db) {
+            return Err(InvalidEqualizerSettings{ reason: "preamp out of range" });
+        }
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_equalizer_set_preamp)(self.ptr, db) == 0 {
+                Ok(())
+            }else{
+                Err(InvalidEqualizerSettings{ reason: "could not set the preamp" })
+            }
+        }
+    }
+
+    /// Get the amplification (in dB) of a band, None if the band does not exist.
+    pub fn amp(&self, band: u32) -> Option<f32> {
+        let db = unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_get_amp_at_index)(self.ptr, band) };
+        if db.is_nan() { None }else{ Some(db) }
+    }
+
+    /// Set the amplification (in dB) of a band, between `EQUALIZER_MIN_AMP` and `EQUALIZER_MAX_AMP`.
+    pub fn set_amp(&mut self, band: u32, db: f32) -> Result<(), InvalidEqualizerSettings> {
+        if !valid_amp(db) {
+            return Err(InvalidEqualizerSettings{ reason: "band amplification out of range" });
+        }
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_equalizer_set_amp_at_index)(self.ptr, db, band) == 0 {
+                Ok(())
+            }else{
+                Err(InvalidEqualizerSettings{ reason: "band does not exist" })
+            }
+        }
+    }
+
+    /// Returns the current settings, to be saved and restored with `from_settings()`.
+    pub fn settings(&self) -> EqualizerSettings {
+        let count = unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_get_band_count)() };
+        EqualizerSettings{
+            preamp: self.preamp(),
+            amps: (0..count).map(|band| self.amp(band).unwrap_or(0.0)).collect(),
+        }
+    }
+
+    /// Returns raw pointer
+    pub fn raw(&self) -> *mut sys::libvlc_equalizer_t {
+        self.ptr
+    }
+}
+
+impl Drop for Equalizer {
+    fn drop(&mut self) {
+        unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_release)(self.ptr) };
+    }
+}
+
+/// Plain values of an `Equalizer`, e.g. to persist a user's curve.
+///
+/// The text form (`Display` / `FromStr`) is the preamp followed by the band amplifications,
+/// all in dB: `"12;-1.5,0,0,3,..."`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct EqualizerSettings {
+    pub preamp: f32,
+    /// Amplification of each band, in the order of `Equalizer::bands()`
+    pub amps: Vec<f32>,
+}
+
+impl EqualizerSettings {
+    fn validate(&self) -> Result<(), InvalidEqualizerSettings> {
+        let count = unsafe{ (sys::get_vlc_dll().libvlc_audio_equalizer_get_band_count)() };
+        self.validate_bands(count as usize)
+    }
+
+    fn validate_bands(&self, count: usize) -> Result<(), InvalidEqualizerSettings> {
+        if !valid_amp(self.preamp) {
+            return Err(InvalidEqualizerSettings{ reason: "preamp out of range" });
+        }
+        if self.amps.len() != count {
+            return Err(InvalidEqualizerSettings{ reason: "wrong number of bands" });
+        }
+        if !self.amps.iter().all(|&db| valid_amp(db)) {
+            return Err(InvalidEqualizerSettings{ reason: "band amplification out of range" });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EqualizerSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{};", self.preamp)?;
+        for (i, db) in self.amps.iter().enumerate() {
+            if i > 0 { write!(f, ",")?; }
+            write!(f, "{}", db)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EqualizerSettings {
+    type Err = InvalidEqualizerSettings;
+
+    fn from_str(s: &str) -> Result<EqualizerSettings, InvalidEqualizerSettings> {
+        let invalid = InvalidEqualizerSettings{ reason: "not a number" };
+        let mut parts = s.trim().splitn(2, ';');
+        let preamp = parts.next().unwrap_or("").trim().parse().map_err(|_| invalid.clone())?;
+        let amps = match parts.next().map(str::trim) {
+            Some("") | None => Vec::new(),
+            Some(amps) => amps.split(',')
+                .map(|db| db.trim().parse().map_err(|_| invalid.clone()))
+                .collect::<Result<Vec<f32>, _>>()?,
+        };
+        Ok(EqualizerSettings{ preamp, amps })
+    }
+}
+
+/// Error returned when equalizer settings can not be parsed or applied.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidEqualizerSettings {
+    pub reason: &'static str,
+}
+
+impl fmt::Display for InvalidEqualizerSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid equalizer settings: {}", self.reason)
+    }
+}
+
+impl ::std::error::Error for InvalidEqualizerSettings {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip() {
+        let settings = EqualizerSettings{ preamp: 12.0, amps: vec![-1.5, 0.0, 0.25, 3.0, -20.0, 20.0] };
+        assert_eq!(settings.to_string(), "12;-1.5,0,0.25,3,-20,20");
+        assert_eq!(settings.to_string().parse(), Ok(settings));
+        assert_eq!(" 1 ; 2 , 3 ".parse(), Ok(EqualizerSettings{ preamp: 1.0, amps: vec![2.0, 3.0] }));
+        assert_eq!("0;".parse(), Ok(EqualizerSettings{ preamp: 0.0, amps: Vec::new() }));
+        assert_eq!("0".parse(), Ok(EqualizerSettings{ preamp: 0.0, amps: Vec::new() }));
+    }
+
+    #[test]
+    fn settings_reject_garbage() {
+        for s in &["", ";1,2", "abc", "1;2,,3", "1;2,x", "1;2;3"] {
+            assert!(s.parse::<EqualizerSettings>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn settings_validate_bands() {
+        let settings = EqualizerSettings{ preamp: 0.0, amps: vec![0.0; 10] };
+        assert!(settings.validate_bands(10).is_ok());
+        assert_eq!(settings.validate_bands(9).unwrap_err().reason, "wrong number of bands");
+        let settings = EqualizerSettings{ preamp: 20.5, amps: vec![0.0; 10] };
+        assert_eq!(settings.validate_bands(10).unwrap_err().reason, "preamp out of range");
+        let settings = EqualizerSettings{ preamp: 0.0, amps: vec![-21.0; 10] };
+        assert_eq!(settings.validate_bands(10).unwrap_err().reason, "band amplification out of range");
+        let settings = EqualizerSettings{ preamp: f32::NAN, amps: vec![0.0; 10] };
+        assert!(settings.validate_bands(10).is_err());
+    }
+}
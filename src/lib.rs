@@ -3,20 +3,40 @@ extern crate libc;
 pub mod sys;
 
 mod enums;
+mod audio;
+mod audio_sink;
 mod core;
+mod equalizer;
 mod tools;
 mod media;
+mod media_metadata;
+mod media_options;
+mod media_stats;
 mod media_list;
 mod media_library;
 mod media_player;
+mod mrl;
+mod overlay;
+mod snapshot;
 mod video;
+mod video_sink;
 mod vlm;
 
 pub use crate::enums::*;
+pub use crate::audio::*;
+pub use crate::audio_sink::*;
 pub use crate::core::*;
+pub use crate::equalizer::*;
 pub use crate::media::*;
+pub use crate::media_metadata::*;
+pub use crate::media_options::*;
+pub use crate::media_stats::*;
 pub use crate::media_list::*;
 pub use crate::media_library::*;
 pub use crate::media_player::*;
+pub use crate::mrl::*;
+pub use crate::overlay::*;
+pub use crate::snapshot::*;
 pub use crate::video::*;
+pub use crate::video_sink::*;
 pub use crate::vlm::*;
@@ -3,10 +3,23 @@
 // Licensed under the MIT license, see the LICENSE file.
 
 use crate::sys;
-use crate::{Instance, EventManager};
-use crate::enums::{State, Meta, TrackType};
+use crate::{Instance, EventManager, MediaList};
+use crate::enums::{State, Meta, TrackType, EventType, ParseFlags, ParsedStatus, SlaveKind, MediaType, Orientation, Projection};
+use crate::media_options::{MediaOptions, MediaOptionFlags, InvalidMediaOption};
+use crate::media_stats::MediaStats;
+use crate::media_metadata::MediaMetadata;
+use crate::mrl::Mrl;
+use crate::core::wait_for_events;
 use crate::tools::{to_cstr, from_cstr, path_to_cstr};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::io::{self, Read, Seek, SeekFrom, Cursor};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::any::Any;
+use std::time::Duration;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use libc::{c_void, c_int, c_uchar, size_t, ssize_t};
 
 pub struct Media {
     pub(crate) ptr: *mut sys::libvlc_media_t,
@@ -29,7 +42,17 @@ impl Media {
         }
     }
 
+    /// Create a media from an `Mrl`, adding its input options to the media.
+    pub fn new_mrl(instance: &Instance, mrl: &Mrl) -> Option<Media> {
+        let md = Media::new_location(instance, &mrl.to_string())?;
+        for option in mrl.options() {
+            md.add_option(option);
+        }
+        Some(md)
+    }
+
     /// Create a media for a certain file path.
+    /// On Unix, paths that are not valid UTF-8 are passed to libvlc unchanged.
     pub fn new_path<T: AsRef<Path>>(instance: &Instance, path: T) -> Option<Media> {
         let cstr = match path_to_cstr(path.as_ref()) {
             Ok(s) => s,
@@ -46,6 +69,13 @@ impl Media {
         }
     }
 
+    /// Create a media for a file path, with its sidecar subtitle and audio files added as slaves.
+    pub fn new_path_with_sidecars<T: AsRef<Path>>(instance: &Instance, path: T) -> Option<Media> {
+        let md = Media::new_path(instance, path)?;
+        md.add_sidecar_slaves();
+        Some(md)
+    }
+
     pub fn new_fd(instance: &Instance, fd: i32) -> Option<Media> {
         unsafe{
             let p = (sys::get_vlc_dll().libvlc_media_new_fd)(instance.ptr, fd);
@@ -57,6 +87,154 @@ impl Media {
         }
     }
 
+    /// Create a media reading its data from a custom `Read + Seek` source.
+    ///
+    /// The reader is kept alive until libvlc frees the media (i.e. until the last
+    /// `Media` / `MediaPlayer` / `MediaList` referencing it is dropped).
+    ///
+    /// libvlc treats a read error as the end of the stream, so playback ends with
+    /// `MediaPlayerEndReached`. Check `take_source_error()` on that event to tell
+    /// an error from the real end of the data.
+    ///
+    /// The reader has a single stream position, so the media can only be read by one input
+    /// at a time: playing it on two players, or parsing it while it is played, fails to open.
+    pub fn from_reader<R: Read + Seek + Send + 'static>(instance: &Instance, reader: R) -> Option<Media> {
+        let user_data = Box::into_raw(Box::new(MediaUserData {
+            source: Some(MediaSource {
+                reader: Mutex::new(Box::new(reader)),
+                error: Mutex::new(None),
+                open: AtomicBool::new(false),
+            }),
+            data: Mutex::new(None),
+            options: Mutex::new(Vec::new()),
+        }));
+
+        unsafe{
+            let source = (*user_data).source.as_ref().unwrap() as *const MediaSource;
+            let p = (sys::get_vlc_dll().libvlc_media_new_callbacks)(
+                instance.ptr,
+                Some(media_source_open),
+                Some(media_source_read),
+                Some(media_source_seek),
+                Some(media_source_close),
+                source as *mut c_void);
+
+            if p.is_null() {
+                drop(Box::from_raw(user_data));
+                return None;
+            }
+
+            // The source is released together with the libvlc_media_t, not with this handle
+            if !attach_user_data(p, user_data) {
+                (sys::get_vlc_dll().libvlc_media_release)(p);
+                drop(Box::from_raw(user_data));
+                return None;
+            }
+
+            Some(Media{ptr: p})
+        }
+    }
+
+    /// Create a media playing an in-memory buffer.
+    pub fn from_bytes(instance: &Instance, bytes: Arc<[u8]>) -> Option<Media> {
+        Media::from_reader(instance, Cursor::new(bytes))
+    }
+
+    /// Take the last I/O error of a media created with `from_reader()` / `from_bytes()`.
+    /// Returns None if no error occurred or the media was not created from a reader.
+    pub fn take_source_error(&self) -> Option<io::Error> {
+        let user_data = unsafe{ get_user_data(self.ptr) }?;
+        user_data.source.as_ref()?.error.lock().ok()?.take()
+    }
+
+    /// Attach a value to the media, replacing any previous value.
+    ///
+    /// The value is shared by every handle to the same libvlc media (e.g. the one returned
+    /// by `MediaPlayer::get_media()`) and dropped when libvlc frees the media.
+    /// Fails if the value can not be tied to the lifetime of the libvlc media.
+    pub fn set_data<T: Any + Send + Sync>(&self, value: T) -> Result<(), ()> {
+        let user_data = unsafe{ get_or_init_user_data(self.ptr) }.ok_or(())?;
+        *user_data.data.lock().map_err(|_| ())? = Some(Arc::new(value));
+        Ok(())
+    }
+
+    /// Get the value attached with `set_data()`, None if there is none or it is not a `T`.
+    pub fn data<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        unsafe{ media_data(self.ptr) }
+    }
+
+    /// Remove the value attached with `set_data()`.
+    pub fn remove_data(&self) {
+        if let Some(user_data) = unsafe{ get_user_data(self.ptr) } {
+            if let Ok(mut data) = user_data.data.lock() {
+                data.take();
+            }
+        }
+    }
+
+    /// Add an option to the media, e.g. `:no-audio` or `:start-time=10`.
+    /// The options are applied when the media is played.
+    pub fn add_option(&self, option: &str) {
+        unsafe{
+            (sys::get_vlc_dll().libvlc_media_add_option)(self.ptr, to_cstr(option).as_ptr());
+            record_option(self.ptr, option, None);
+        }
+    }
+
+    /// Add an option to the media with configurable flags.
+    pub fn add_option_flag(&self, option: &str, flags: MediaOptionFlags) {
+        unsafe{
+            (sys::get_vlc_dll().libvlc_media_add_option_flag)(self.ptr, to_cstr(option).as_ptr(), flags.bits());
+            record_option(self.ptr, option, Some(flags));
+        }
+    }
+
+    /// Validate and add all options of a `MediaOptions` builder.
+    /// Nothing is added if one of the options is invalid.
+    pub fn apply_options(&self, options: &MediaOptions) -> Result<(), InvalidMediaOption> {
+        for option in options.to_options()? {
+            self.add_option(&option);
+        }
+        Ok(())
+    }
+
+    /// Create a new, independent media for the same MRL, with the options added through
+    /// `add_option()`, `add_option_flag()` and `apply_options()`.
+    ///
+    /// Options added to the duplicate do not affect `self`, so the same file can e.g. be
+    /// queued twice with different `:start-time` values.
+    ///
+    /// Limits:
+    /// - libvlc does not expose the instance of a media, so it has to be passed again.
+    /// - Only options added through this API are copied. Options set by libvlc itself
+    ///   (e.g. `#EXTVLCOPT` lines of a playlist, for subitems), slaves and meta data are not.
+    /// - Returns None for media created with `from_reader()` / `from_bytes()`,
+    ///   their reader can not be shared.
+    pub fn duplicate(&self, instance: &Instance) -> Option<Media> {
+        let user_data = unsafe{ get_user_data(self.ptr) };
+        if user_data.is_some_and(|u| u.source.is_some()) {
+            return None;
+        }
+
+        let options = match user_data {
+            Some(u) => u.options.lock().ok()?.clone(),
+            None => Vec::new(),
+        };
+        let md = Media::new_location(instance, &self.mrl()?)?;
+        for (option, flags) in options {
+            match flags {
+                Some(flags) => md.add_option_flag(&option, flags),
+                None => md.add_option(&option),
+            }
+        }
+        Some(md)
+    }
+
+    /// Get the media resource locator as a parsed `Mrl`.
+    pub fn parsed_mrl(&self) -> Option<Mrl> {
+        Mrl::parse(&self.mrl()?)
+    }
+
     pub fn mrl(&self) -> Option<String> {
         unsafe{
             let p_str = (sys::get_vlc_dll().libvlc_media_get_mrl)(self.ptr);
@@ -98,11 +276,53 @@ impl Media {
         if unsafe{ (sys::get_vlc_dll().libvlc_media_save_meta)(self.ptr) } == 0 { false }else{ true }
     }
 
+    /// Read all meta data of the media.
+    /// If the media has not yet been parsed, most fields will be None.
+    pub fn metadata(&self) -> MediaMetadata {
+        let mut metadata = MediaMetadata::default();
+        for meta in MediaMetadata::KEYS.iter() {
+            metadata.set(*meta, self.get_meta(*meta));
+        }
+        metadata
+    }
+
+    /// Set and save all fields of `metadata` that differ from the current meta data.
+    ///
+    /// Fields that are None are cleared. On failure, returns the changed fields that
+    /// could not be written (all changed fields if saving failed).
+    pub fn write_metadata(&self, metadata: &MediaMetadata) -> Result<(), Vec<Meta>> {
+        let changed = self.metadata().diff(metadata);
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        for meta in &changed {
+            match metadata.get(*meta) {
+                Some(value) => self.set_meta(*meta, value),
+                None => unsafe{ (sys::get_vlc_dll().libvlc_media_set_meta)(self.ptr, *meta, ::std::ptr::null()) },
+            }
+        }
+
+        if !self.save_meta() {
+            return Err(changed);
+        }
+
+        let written = self.metadata();
+        let failed: Vec<Meta> = changed.into_iter().filter(|m| written.get(*m) != metadata.get(*m)).collect();
+        if failed.is_empty() { Ok(()) }else{ Err(failed) }
+    }
+
     /// Get current state of media descriptor object.
     pub fn state(&self) -> State {
         unsafe{ (sys::get_vlc_dll().libvlc_media_get_state)(self.ptr) }
     }
 
+    /// Get the media type of the media descriptor object.
+    /// The type is only reliable after the media has been parsed.
+    pub fn media_type(&self) -> MediaType {
+        unsafe{ (sys::get_vlc_dll().libvlc_media_get_type)(self.ptr) }
+    }
+
     /// Get duration (in ms) of media descriptor object item.
     pub fn duration(&self) -> Option<i64> {
         let time = unsafe{
@@ -111,6 +331,18 @@ impl Media {
         if time != -1 { Some(time) }else{ None }
     }
 
+    /// Get the current statistics about the media.
+    /// Returns None if the media is not being played.
+    pub fn stats(&self) -> Option<MediaStats> {
+        unsafe{
+            let mut stats: sys::libvlc_media_stats_t = ::std::mem::zeroed();
+            if (sys::get_vlc_dll().libvlc_media_get_stats)(self.ptr, &mut stats) == 0 {
+                return None;
+            }
+            Some(MediaStats::from(stats))
+        }
+    }
+
     /// Parse a media.
     pub fn parse(&self) {
         unsafe{ (sys::get_vlc_dll().libvlc_media_parse)(self.ptr) };
@@ -121,11 +353,67 @@ impl Media {
         unsafe{ (sys::get_vlc_dll().libvlc_media_parse_async)(self.ptr) };
     }
 
+    /// Parse the media asynchronously with options.
+    ///
+    /// Emits `Event::MediaParsedChanged` when done. `timeout` is the maximum time allowed
+    /// to preparse the media, None uses the "preparse-timeout" of the instance (5 seconds by default).
+    /// The timeout is rounded up to whole milliseconds, with a minimum of 1 ms.
+    /// Parsing is only done once, calling this on an already parsed media has no effect.
+    pub fn parse_with_async(&self, flags: ParseFlags, timeout: Option<Duration>) -> Result<(), ()> {
+        // 0 would make libvlc wait indefinitely
+        let timeout = match timeout {
+            Some(t) => t.as_micros().div_ceil(1000).clamp(1, i32::MAX as u128) as i32,
+            None => -1,
+        };
+        if unsafe{ (sys::get_vlc_dll().libvlc_media_parse_with_options)(self.ptr, flags.bits(), timeout) } == 0 {
+            Ok(())
+        }else{
+            Err(())
+        }
+    }
+
+    /// Parse the media with options and block until parsing is finished.
+    /// See `parse_with_async()` for the meaning of `timeout`.
+    ///
+    /// libvlc enforces the timeout. If `timeout` is Some, this also gives up shortly after it,
+    /// stops the parsing and returns `ParsedStatus::Timeout`. If it is None, this waits for
+    /// libvlc to end the parsing according to the "preparse-timeout" of the instance.
+    pub fn parse_with(&self, flags: ParseFlags, timeout: Option<Duration>) -> ParsedStatus {
+        let done = self.wait_for_events(
+            &[EventType::MediaParsedChanged],
+            timeout.map(|t| t + PARSE_TIMEOUT_MARGIN),
+            || self.parse_with_async(flags, timeout).is_ok(),
+            // libvlc keeps the previous status until a skipped media is parsed again
+            || self.is_parsed() && self.parsed_status().is_some());
+
+        if !done && !self.is_parsed() {
+            self.parse_stop();
+            return ParsedStatus::Timeout;
+        }
+        self.parsed_status().unwrap_or(ParsedStatus::Failed)
+    }
+
+    /// Parse a local media and block until parsing is finished or the timeout expired.
+    pub fn parse_blocking(&self, timeout: Duration) -> ParsedStatus {
+        self.parse_with(ParseFlags::default(), Some(timeout))
+    }
+
+    /// Stop the parsing of the media.
+    /// `Event::MediaParsedChanged` is emitted with `ParsedStatus::Timeout`.
+    pub fn parse_stop(&self) {
+        unsafe{ (sys::get_vlc_dll().libvlc_media_parse_stop)(self.ptr) };
+    }
+
     /// Get Parsed status for media descriptor object.
     pub fn is_parsed(&self) -> bool {
         if unsafe{ (sys::get_vlc_dll().libvlc_media_is_parsed)(self.ptr) } == 0 { false }else{ true }
     }
 
+    /// Get the parsed status of the media, None if it has not been parsed yet.
+    pub fn parsed_status(&self) -> Option<ParsedStatus> {
+        ParsedStatus::from_raw(unsafe{ (sys::get_vlc_dll().libvlc_media_get_parsed_status)(self.ptr) })
+    }
+
     pub fn tracks(&self) -> Option<Vec<MediaTrack>> {
         unsafe{
             let mut p_track: *mut *mut sys::libvlc_media_track_t = ::std::ptr::null_mut();
@@ -155,19 +443,23 @@ impl Media {
                             sar_den:        (*video).i_sar_den,
                             frame_rate_num: (*video).i_frame_rate_num,
                             frame_rate_den: (*video).i_frame_rate_den,
+                            orientation:    (*video).i_orientation,
+                            projection:     (*video).i_projection,
                         })
                     },
                     TrackType::Text => {
                         let subtitle = (**p).subtitle();
                         MediaTrackUnion::Subtitle(SubtitleTrack{
-                            encoding: from_cstr((*subtitle).psz_encoding)
+                            encoding:    from_cstr((*subtitle).psz_encoding),
+                            language:    from_cstr((**p).psz_language),
+                            description: from_cstr((**p).psz_description),
                         })
                     },
                     TrackType::Unknown => MediaTrackUnion::None,
                 };
                 track.push(MediaTrack{
-                    codec:              (**p).i_codec,
-                    original_fourcc:    (**p).i_original_fourcc,
+                    codec:              FourCC((**p).i_codec),
+                    original_fourcc:    FourCC((**p).i_original_fourcc),
                     id:                 (**p).i_id,
                     track_type:         (**p).i_type,
                     profile:            (**p).i_profile,
@@ -184,22 +476,465 @@ impl Media {
         }
     }
 
+    /// Get subitems of media descriptor object.
+    /// For playlists, directories and archives the subitems are only available after parsing.
+    pub fn subitems(&self) -> Option<MediaList> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_media_subitems)(self.ptr);
+            if p.is_null() { None }else{ Some(MediaList{ptr: p}) }
+        }
+    }
+
+    /// Recursively parse the media and its subitems, up to `depth` levels deep.
+    ///
+    /// Returns the subitems as a flattened tree in depth-first order, the root media
+    /// itself is not included. Each item is parsed with a timeout of `EXPAND_PARSE_TIMEOUT`,
+    /// items that fail to parse are still returned, but not expanded further.
+    /// Media that were skipped by an earlier local-only parse are parsed again with network access.
+    pub fn expand(&self, depth: usize) -> Vec<SubItem> {
+        let mut items = Vec::new();
+        if depth > 0 && self.expand_parse() {
+            if let Some(list) = self.subitems() {
+                expand_list(&list, &mut items, None, 0, depth);
+            }
+        }
+        items
+    }
+
+    // Parse with network access. For playlists and directories, also wait until
+    // libvlc added the subitems, which may be reported after the parsing ended.
+    fn expand_parse(&self) -> bool {
+        let flags = ParseFlags { network: true, ..ParseFlags::default() };
+        let tree_added = AtomicBool::new(false);
+        let has_subitems = || {
+            tree_added.load(Ordering::Acquire) || self.subitems().is_some_and(|list| {
+                list.lock();
+                let count = list.count();
+                list.unlock();
+                count > 0
+            })
+        };
+
+        let done = unsafe{
+            let em = (sys::get_vlc_dll().libvlc_media_event_manager)(self.ptr);
+            wait_for_events(
+                em,
+                &[EventType::MediaParsedChanged, EventType::MediaSubItemAdded, EventType::MediaSubItemTreeAdded],
+                Some(EXPAND_PARSE_TIMEOUT + PARSE_TIMEOUT_MARGIN),
+                |pe| {
+                    if pe._type == EventType::MediaSubItemTreeAdded as i32 {
+                        tree_added.store(true, Ordering::Release);
+                    }
+                    true
+                },
+                || self.parse_with_async(flags, Some(EXPAND_PARSE_TIMEOUT)).is_ok(),
+                || self.is_parsed() && match self.media_type() {
+                    MediaType::Directory | MediaType::Playlist => has_subitems(),
+                    _ => true,
+                })
+        };
+
+        if !done && !self.is_parsed() {
+            self.parse_stop();
+        }
+        self.parsed_status() == Some(ParsedStatus::Done)
+    }
+
+    // `core::wait_for_events()` on the events of this media
+    fn wait_for_events<S, D>(&self, events: &[EventType], timeout: Option<Duration>, start: S, done: D) -> bool
+        where S: FnOnce() -> bool, D: Fn() -> bool
+    {
+        unsafe{
+            let em = (sys::get_vlc_dll().libvlc_media_event_manager)(self.ptr);
+            wait_for_events(em, events, timeout, |_| true, start, done)
+        }
+    }
+
+    /// Add a slave (external subtitle or audio track) to the media.
+    /// `uri` must be a valid URI (e.g. `file:///home/user/movie.srt`),
+    /// `priority` goes from 0 (low) to 4 (high, user-added).
+    /// Must be called before the media is played.
+    pub fn add_slave(&self, kind: SlaveKind, uri: &str, priority: u32) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_media_slaves_add)(self.ptr, kind, priority, to_cstr(uri).as_ptr()) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+
+    /// Get the slaves of the media, both added by the user and found by libvlc.
+    pub fn slaves(&self) -> Vec<MediaSlave> {
+        unsafe{
+            let mut p_slaves: *mut *mut sys::libvlc_media_slave_t = ::std::ptr::null_mut();
+            let n = (sys::get_vlc_dll().libvlc_media_slaves_get)(self.ptr, &mut p_slaves);
+            if n == 0 {
+                return Vec::new();
+            }
+
+            let slaves = (0..n as usize).map(|i| {
+                let p = *p_slaves.add(i);
+                MediaSlave {
+                    uri: from_cstr((*p).psz_uri).unwrap_or_default(),
+                    kind: (*p).i_type,
+                    priority: (*p).i_priority,
+                }
+            }).collect();
+
+            (sys::get_vlc_dll().libvlc_media_slaves_release)(p_slaves, n);
+            slaves
+        }
+    }
+
+    /// Clear all slaves previously added by `add_slave()` or found by libvlc.
+    pub fn clear_slaves(&self) {
+        unsafe{ (sys::get_vlc_dll().libvlc_media_slaves_clear)(self.ptr) };
+    }
+
+    /// Add the sidecar subtitle and audio files of a local media (see `find_sidecar_files()`) as slaves.
+    /// Returns the number of slaves added, 0 if the media is not a local file.
+    pub fn add_sidecar_slaves(&self) -> usize {
+        let path = match self.parsed_mrl().and_then(|mrl| mrl.to_path()) {
+            Some(p) => p,
+            None => { return 0; },
+        };
+        find_sidecar_files(path).into_iter().filter(|sidecar| {
+            match Mrl::from_path(&sidecar.path) {
+                Some(mrl) => self.add_slave(sidecar.kind, &mrl.to_string(), sidecar.priority).is_ok(),
+                None => false,
+            }
+        }).count()
+    }
+
     /// Returns raw pointer
     pub fn raw(&self) -> *mut sys::libvlc_media_t {
         self.ptr
     }
 }
 
+/// Media compare equal if they refer to the same libvlc media object.
+impl PartialEq for Media {
+    fn eq(&self, other: &Media) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for Media {}
+
+impl Hash for Media {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ptr.hash(state);
+    }
+}
+
 impl Drop for Media {
     fn drop(&mut self) {
         unsafe{ (sys::get_vlc_dll().libvlc_media_release)(self.ptr) };
     }
 }
 
+/// A slave of a media, see `Media::slaves()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MediaSlave {
+    pub uri: String,
+    pub kind: SlaveKind,
+    pub priority: u32,
+}
+
+/// A subtitle or audio file found next to a media file, see `find_sidecar_files()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SidecarFile {
+    pub path: PathBuf,
+    pub kind: SlaveKind,
+    /// 4 for an exact name match (`movie.srt`), 3 for a tagged match (`movie.en.srt`)
+    pub priority: u32,
+}
+
+const SUBTITLE_EXTENSIONS: &[&str] = &[
+    "srt", "ass", "ssa", "sub", "idx", "vtt", "smi", "usf", "jss", "psb", "rt", "ttml", "dfxp",
+];
+
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "ac3", "eac3", "dts", "aac", "mka", "mp3", "flac", "ogg", "opus", "wav", "m4a",
+];
+
+/// Find subtitle and audio files next to `path` sharing its file stem,
+/// e.g. `movie.srt`, `movie.en.ass` or `movie.ac3` for `movie.mkv`.
+/// The result is sorted by priority (highest first), then by path.
+pub fn find_sidecar_files<T: AsRef<Path>>(path: T) -> Vec<SidecarFile> {
+    let path = path.as_ref();
+    // Compared as bytes, so paths that are not valid UTF-8 work too
+    let stem = match path.file_stem() {
+        Some(s) => s.as_encoded_bytes(),
+        None => { return Vec::new(); },
+    };
+    let dir = match path.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d,
+        _ => Path::new("."),
+    };
+    let entries = match ::std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => { return Vec::new(); },
+    };
+
+    let mut sidecars: Vec<SidecarFile> = entries.filter_map(|entry| {
+        let candidate = entry.ok()?.path();
+        if candidate.file_name() == path.file_name() || !candidate.is_file() {
+            return None;
+        }
+
+        let name = candidate.file_name()?.as_encoded_bytes();
+        let rest = name.strip_prefix(stem)?.strip_prefix(b".")?;
+        let extension = candidate.extension()?.to_str()?.to_lowercase();
+        let kind = if SUBTITLE_EXTENSIONS.contains(&extension.as_str()) {
+            SlaveKind::Subtitle
+        } else if AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+            SlaveKind::Audio
+        } else {
+            return None;
+        };
+        let priority = if rest.contains(&b'.') { 3 } else { 4 };
+
+        Some(SidecarFile { path: candidate, kind, priority })
+    }).collect();
+
+    sidecars.sort_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.path.cmp(&b.path)));
+    sidecars
+}
+
+// Time given to libvlc to report a parse timeout before Media::parse_with() gives up
+const PARSE_TIMEOUT_MARGIN: Duration = Duration::from_secs(1);
+
+/// Parse timeout for each item in `Media::expand()`
+pub const EXPAND_PARSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An item of the flattened tree returned by `Media::expand()`.
+pub struct SubItem {
+    /// Nesting level, 0 for direct subitems of the expanded media
+    pub depth: usize,
+    /// Index of the parent item, None for direct subitems of the expanded media
+    pub parent: Option<usize>,
+    pub mrl: Option<String>,
+    /// Meta data read when the item was parsed
+    pub metadata: MediaMetadata,
+    /// Duration in ms
+    pub duration: Option<i64>,
+    pub media: Media,
+}
+
+fn expand_list(list: &MediaList, items: &mut Vec<SubItem>, parent: Option<usize>, level: usize, depth: usize) {
+    list.lock();
+    let children: Vec<Media> = (0..list.count()).filter_map(|i| list.item_at_index(i)).collect();
+    list.unlock();
+
+    for media in children {
+        let parsed = media.expand_parse();
+        let subitems = if parsed && level + 1 < depth { media.subitems() } else { None };
+
+        let index = items.len();
+        items.push(SubItem {
+            depth: level,
+            parent,
+            mrl: media.mrl(),
+            metadata: media.metadata(),
+            duration: media.duration(),
+            media,
+        });
+
+        if let Some(list) = subitems {
+            expand_list(&list, items, Some(index), level + 1, depth);
+        }
+    }
+}
+
+// For Media::from_reader
+trait ReadSeek: Read + Seek + Send {}
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+struct MediaSource {
+    reader: Mutex<Box<dyn ReadSeek>>,
+    error: Mutex<Option<io::Error>>,
+    // There is only one stream position, so only one input may read at a time
+    open: AtomicBool,
+}
+
+impl MediaSource {
+    fn set_error(&self, e: io::Error) {
+        if let Ok(mut error) = self.error.lock() {
+            *error = Some(e);
+        }
+    }
+}
+
+unsafe extern "C" fn media_source_open(opaque: *mut c_void, datap: *mut *mut c_void, sizep: *mut u64) -> c_int {
+    let source = &*(opaque as *const MediaSource);
+    *datap = opaque;
+    *sizep = u64::MAX;
+
+    if source.open.swap(true, Ordering::AcqRel) {
+        return -1;
+    }
+
+    let mut reader = match source.reader.lock() {
+        Ok(r) => r,
+        Err(_) => { source.open.store(false, Ordering::Release); return -1; },
+    };
+
+    // Determine the stream size if the reader supports seeking to the end
+    if let Ok(size) = reader.seek(SeekFrom::End(0)) {
+        *sizep = size;
+    }
+
+    // libvlc does not call close if open fails
+    match reader.seek(SeekFrom::Start(0)) {
+        Ok(_) => 0,
+        Err(e) => { source.set_error(e); source.open.store(false, Ordering::Release); -1 },
+    }
+}
+
+unsafe extern "C" fn media_source_read(opaque: *mut c_void, buf: *mut c_uchar, len: size_t) -> ssize_t {
+    let source = &*(opaque as *const MediaSource);
+    let buf = ::std::slice::from_raw_parts_mut(buf, len);
+
+    let mut reader = match source.reader.lock() {
+        Ok(r) => r,
+        Err(_) => { return -1; },
+    };
+
+    loop {
+        match reader.read(buf) {
+            Ok(n) => { return n as ssize_t; },
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => { source.set_error(e); return -1; },
+        }
+    }
+}
+
+unsafe extern "C" fn media_source_seek(opaque: *mut c_void, offset: u64) -> c_int {
+    let source = &*(opaque as *const MediaSource);
+
+    let mut reader = match source.reader.lock() {
+        Ok(r) => r,
+        Err(_) => { return -1; },
+    };
+
+    match reader.seek(SeekFrom::Start(offset)) {
+        Ok(_) => 0,
+        Err(e) => { source.set_error(e); -1 },
+    }
+}
+
+unsafe extern "C" fn media_source_close(opaque: *mut c_void) {
+    // The source may be opened again, it is released on MediaFreed
+    let source = &*(opaque as *const MediaSource);
+    source.open.store(false, Ordering::Release);
+}
+
+// Per-media state stored as the libvlc user data, released on MediaFreed
+struct MediaUserData {
+    source: Option<MediaSource>,
+    data: Mutex<Option<Arc<dyn Any + Send + Sync>>>,
+    // Options added through the Media API, None flags for `add_option()`
+    options: Mutex<Vec<(String, Option<MediaOptionFlags>)>>,
+}
+
+// Serializes the lazy creation of MediaUserData, the same libvlc_media_t can
+// be wrapped by several Media handles on different threads
+static USER_DATA_LOCK: Mutex<()> = Mutex::new(());
+
+unsafe fn get_user_data<'a>(md: *mut sys::libvlc_media_t) -> Option<&'a MediaUserData> {
+    let p = (sys::get_vlc_dll().libvlc_media_get_user_data)(md) as *const MediaUserData;
+    if p.is_null() { None }else{ Some(&*p) }
+}
+
+unsafe fn get_or_init_user_data<'a>(md: *mut sys::libvlc_media_t) -> Option<&'a MediaUserData> {
+    let _guard = USER_DATA_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(user_data) = get_user_data(md) {
+        return Some(user_data);
+    }
+
+    let user_data = Box::into_raw(Box::new(MediaUserData{
+        source: None,
+        data: Mutex::new(None),
+        options: Mutex::new(Vec::new()),
+    }));
+    if attach_user_data(md, user_data) {
+        Some(&*user_data)
+    }else{
+        drop(Box::from_raw(user_data));
+        None
+    }
+}
+
+// Set `user_data` as the user data of `md` and release it when libvlc frees the media.
+unsafe fn attach_user_data(md: *mut sys::libvlc_media_t, user_data: *mut MediaUserData) -> bool {
+    let em = (sys::get_vlc_dll().libvlc_media_event_manager)(md);
+    if em.is_null() || (sys::get_vlc_dll().libvlc_event_attach)(
+        em, EventType::MediaFreed as i32, media_user_data_freed, user_data as *mut c_void) != 0 {
+        return false;
+    }
+    (sys::get_vlc_dll().libvlc_media_set_user_data)(md, user_data as *mut c_void);
+    true
+}
+
+// Remember an option for `Media::duplicate()`
+unsafe fn record_option(md: *mut sys::libvlc_media_t, option: &str, flags: Option<MediaOptionFlags>) {
+    if let Some(user_data) = get_or_init_user_data(md) {
+        if let Ok(mut options) = user_data.options.lock() {
+            options.push((option.to_owned(), flags));
+        }
+    }
+}
+
+pub(crate) unsafe fn media_data<T: Any + Send + Sync>(md: *mut sys::libvlc_media_t) -> Option<Arc<T>> {
+    let user_data = get_user_data(md)?;
+    let data = user_data.data.lock().ok()?.clone()?;
+    data.downcast::<T>().ok()
+}
+
+unsafe extern "C" fn media_user_data_freed(_pe: *const sys::libvlc_event_t, data: *mut c_void) {
+    drop(Box::from_raw(data as *mut MediaUserData));
+}
+
+/// A codec FourCC, e.g. `h264` or `mp4a`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FourCC(pub u32);
+
+impl FourCC {
+    pub fn from_bytes(bytes: [u8; 4]) -> FourCC {
+        FourCC(u32::from_le_bytes(bytes))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+
+    /// Get a human-readable description of the codec, e.g. "H264 - MPEG-4 AVC (part 10)".
+    pub fn description(&self, track_type: TrackType) -> Option<String> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_media_get_codec_description)(track_type, self.0);
+            from_cstr(p).filter(|s| !s.is_empty())
+        }
+    }
+}
+
+impl From<u32> for FourCC {
+    fn from(fourcc: u32) -> FourCC {
+        FourCC(fourcc)
+    }
+}
+
+impl fmt::Display for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.to_bytes().iter() {
+            let c = if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct MediaTrack {
-    pub codec: u32,
-    pub original_fourcc: u32,
+    pub codec: FourCC,
+    pub original_fourcc: FourCC,
     pub id: i32,
     pub track_type: TrackType,
     pub profile: i32,
@@ -210,6 +945,13 @@ pub struct MediaTrack {
     pub type_specific_data: MediaTrackUnion,
 }
 
+impl MediaTrack {
+    /// Human-readable description of the track codec.
+    pub fn codec_description(&self) -> Option<String> {
+        self.codec.description(self.track_type)
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum MediaTrackUnion {
     Audio(AudioTrack), Video(VideoTrack), Subtitle(SubtitleTrack), None,
@@ -229,10 +971,65 @@ pub struct VideoTrack {
     pub sar_den: u32,
     pub frame_rate_num: u32,
     pub frame_rate_den: u32,
+    pub orientation: Orientation,
+    pub projection: Projection,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct SubtitleTrack {
     pub encoding: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = ::std::env::temp_dir().join(format!("vlc-rs-test-{}-{}", name, ::std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_sidecar_files() {
+        let dir = temp_dir("sidecars");
+        for name in &["movie.mkv", "movie.srt", "movie.en.ass", "movie.AC3", "movie.txt", "movies.srt", "other.srt"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        fs::create_dir(dir.join("movie.vtt")).unwrap();
+
+        let found: Vec<(PathBuf, SlaveKind, u32)> = find_sidecar_files(dir.join("movie.mkv")).into_iter()
+            .map(|s| (s.path, s.kind, s.priority))
+            .collect();
+        assert_eq!(found, vec![
+            (dir.join("movie.AC3"), SlaveKind::Audio, 4),
+            (dir.join("movie.srt"), SlaveKind::Subtitle, 4),
+            (dir.join("movie.en.ass"), SlaveKind::Subtitle, 3),
+        ]);
+        assert!(find_sidecar_files(dir.join("missing").join("movie.mkv")).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_sidecar_files_of_non_utf8_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = temp_dir("sidecars-non-utf8");
+        let movie = dir.join(OsStr::from_bytes(b"m\xffvie.mkv"));
+        let subtitle = dir.join(OsStr::from_bytes(b"m\xffvie.fr.srt"));
+        fs::write(&movie, b"").unwrap();
+        fs::write(&subtitle, b"").unwrap();
+
+        let found: Vec<PathBuf> = find_sidecar_files(&movie).into_iter().map(|s| s.path).collect();
+        assert_eq!(found, vec![subtitle]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::enums::Meta;
+
+/// All meta data of a media, see `Media::metadata()` and `Media::write_metadata()`.
+///
+/// libvlc stores every field as a string, fields that are not set are None.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct MediaMetadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub genre: Option<String>,
+    pub copyright: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<String>,
+    pub description: Option<String>,
+    pub rating: Option<String>,
+    pub date: Option<String>,
+    pub setting: Option<String>,
+    pub url: Option<String>,
+    pub language: Option<String>,
+    pub now_playing: Option<String>,
+    pub publisher: Option<String>,
+    pub encoded_by: Option<String>,
+    pub artwork_url: Option<String>,
+    pub track_id: Option<String>,
+    pub track_total: Option<String>,
+    pub director: Option<String>,
+    pub season: Option<String>,
+    pub episode: Option<String>,
+    pub show_name: Option<String>,
+    pub actors: Option<String>,
+    pub album_artist: Option<String>,
+    pub disc_number: Option<String>,
+    pub disc_total: Option<String>,
+}
+
+impl MediaMetadata {
+    /// Every `Meta` key, in libvlc order.
+    pub const KEYS: [Meta; 26] = [
+        Meta::Title, Meta::Artist, Meta::Genre, Meta::Copyright, Meta::Album,
+        Meta::TrackNumber, Meta::Description, Meta::Rating, Meta::Date, Meta::Setting,
+        Meta::URL, Meta::Language, Meta::NowPlaying, Meta::Publisher, Meta::EncodedBy,
+        Meta::ArtworkURL, Meta::TrackID, Meta::TrackTotal, Meta::Director, Meta::Season,
+        Meta::Episode, Meta::ShowName, Meta::Actors, Meta::AlbumArtist, Meta::DiscNumber,
+        Meta::DiscTotal,
+    ];
+
+    /// Get the field corresponding to `meta`.
+    pub fn get(&self, meta: Meta) -> Option<&str> {
+        self.field(meta).as_deref()
+    }
+
+    /// Set the field corresponding to `meta`.
+    pub fn set(&mut self, meta: Meta, value: Option<String>) {
+        *self.field_mut(meta) = value;
+    }
+
+    /// Keys whose value differs between `self` and `other`.
+    pub fn diff(&self, other: &MediaMetadata) -> Vec<Meta> {
+        MediaMetadata::KEYS.iter().cloned().filter(|m| self.field(*m) != other.field(*m)).collect()
+    }
+
+    fn field(&self, meta: Meta) -> &Option<String> {
+        match meta {
+            Meta::Title => &self.title,
+            Meta::Artist => &self.artist,
+            Meta::Genre => &self.genre,
+            Meta::Copyright => &self.copyright,
+            Meta::Album => &self.album,
+            Meta::TrackNumber => &self.track_number,
+            Meta::Description => &self.description,
+            Meta::Rating => &self.rating,
+            Meta::Date => &self.date,
+            Meta::Setting => &self.setting,
+            Meta::URL => &self.url,
+            Meta::Language => &self.language,
+            Meta::NowPlaying => &self.now_playing,
+            Meta::Publisher => &self.publisher,
+            Meta::EncodedBy => &self.encoded_by,
+            Meta::ArtworkURL => &self.artwork_url,
+            Meta::TrackID => &self.track_id,
+            Meta::TrackTotal => &self.track_total,
+            Meta::Director => &self.director,
+            Meta::Season => &self.season,
+            Meta::Episode => &self.episode,
+            Meta::ShowName => &self.show_name,
+            Meta::Actors => &self.actors,
+            Meta::AlbumArtist => &self.album_artist,
+            Meta::DiscNumber => &self.disc_number,
+            Meta::DiscTotal => &self.disc_total,
+        }
+    }
+
+    fn field_mut(&mut self, meta: Meta) -> &mut Option<String> {
+        match meta {
+            Meta::Title => &mut self.title,
+            Meta::Artist => &mut self.artist,
+            Meta::Genre => &mut self.genre,
+            Meta::Copyright => &mut self.copyright,
+            Meta::Album => &mut self.album,
+            Meta::TrackNumber => &mut self.track_number,
+            Meta::Description => &mut self.description,
+            Meta::Rating => &mut self.rating,
+            Meta::Date => &mut self.date,
+            Meta::Setting => &mut self.setting,
+            Meta::URL => &mut self.url,
+            Meta::Language => &mut self.language,
+            Meta::NowPlaying => &mut self.now_playing,
+            Meta::Publisher => &mut self.publisher,
+            Meta::EncodedBy => &mut self.encoded_by,
+            Meta::ArtworkURL => &mut self.artwork_url,
+            Meta::TrackID => &mut self.track_id,
+            Meta::TrackTotal => &mut self.track_total,
+            Meta::Director => &mut self.director,
+            Meta::Season => &mut self.season,
+            Meta::Episode => &mut self.episode,
+            Meta::ShowName => &mut self.show_name,
+            Meta::Actors => &mut self.actors,
+            Meta::AlbumArtist => &mut self.album_artist,
+            Meta::DiscNumber => &mut self.disc_number,
+            Meta::DiscTotal => &mut self.disc_total,
+        }
+    }
+}
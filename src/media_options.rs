@@ -0,0 +1,290 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Flags for `Media::add_option_flag()`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct MediaOptionFlags {
+    /// The option is trusted, even if it is considered unsafe by libvlc (e.g. `:sout`).
+    pub trusted: bool,
+    /// The option replaces any previous value of the same option instead of being appended.
+    pub unique: bool,
+}
+
+impl MediaOptionFlags {
+    pub(crate) fn bits(&self) -> u32 {
+        let mut bits = 0;
+        if self.trusted { bits |= crate::sys::libvlc_media_option_trusted; }
+        if self.unique { bits |= crate::sys::libvlc_media_option_unique; }
+        bits
+    }
+}
+
+/// Error returned when a `MediaOptions` value can not be rendered.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidMediaOption {
+    /// Name of the offending option (without the leading `:`)
+    pub option: &'static str,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for InvalidMediaOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid media option \":{}\": {}", self.option, self.reason)
+    }
+}
+
+impl ::std::error::Error for InvalidMediaOption {}
+
+/// Typed builder for common per-media input options.
+///
+/// ```no_run
+/// # use vlc_static::*;
+/// # let instance = Instance::new().unwrap();
+/// let md = Media::new_path(&instance, "movie.mkv").unwrap();
+/// let options = MediaOptions::new()
+///     .start_time(10.0)
+///     .stop_time(20.0)
+///     .no_audio();
+/// md.apply_options(&options).unwrap();
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct MediaOptions {
+    start_time: Option<f64>,
+    stop_time: Option<f64>,
+    run_time: Option<f64>,
+    input_repeat: Option<u32>,
+    network_caching: Option<u32>,
+    file_caching: Option<u32>,
+    sub_file: Option<PathBuf>,
+    no_audio: bool,
+    no_video: bool,
+    no_spu: bool,
+    sout: Option<String>,
+    extra: Vec<String>,
+}
+
+impl MediaOptions {
+    pub fn new() -> MediaOptions {
+        MediaOptions::default()
+    }
+
+    /// Start playback at the given time (in seconds).
+    pub fn start_time(mut self, seconds: f64) -> Self {
+        self.start_time = Some(seconds);
+        self
+    }
+
+    /// Stop playback at the given time (in seconds).
+    pub fn stop_time(mut self, seconds: f64) -> Self {
+        self.stop_time = Some(seconds);
+        self
+    }
+
+    /// Stop playback after playing for the given duration (in seconds).
+    pub fn run_time(mut self, seconds: f64) -> Self {
+        self.run_time = Some(seconds);
+        self
+    }
+
+    /// Repeat the input the given number of times.
+    pub fn input_repeat(mut self, count: u32) -> Self {
+        self.input_repeat = Some(count);
+        self
+    }
+
+    /// Caching value for network resources (in ms).
+    pub fn network_caching(mut self, ms: u32) -> Self {
+        self.network_caching = Some(ms);
+        self
+    }
+
+    /// Caching value for local files (in ms).
+    pub fn file_caching(mut self, ms: u32) -> Self {
+        self.file_caching = Some(ms);
+        self
+    }
+
+    /// Load an external subtitle file.
+    pub fn sub_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.sub_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Disable audio output for this media.
+    pub fn no_audio(mut self) -> Self {
+        self.no_audio = true;
+        self
+    }
+
+    /// Disable video output for this media.
+    pub fn no_video(mut self) -> Self {
+        self.no_video = true;
+        self
+    }
+
+    /// Disable subtitles for this media.
+    pub fn no_spu(mut self) -> Self {
+        self.no_spu = true;
+        self
+    }
+
+    /// Stream output chain, e.g. `#transcode{vcodec=h264}:std{access=file,dst=out.mp4}`.
+    pub fn sout(mut self, chain: &str) -> Self {
+        self.sout = Some(chain.to_owned());
+        self
+    }
+
+    /// Any other option, in the `:option=value` form libvlc expects.
+    pub fn option(mut self, option: &str) -> Self {
+        self.extra.push(option.to_owned());
+        self
+    }
+
+    /// Validate the options and render them as `:option=value` strings.
+    pub fn to_options(&self) -> Result<Vec<String>, InvalidMediaOption> {
+        fn seconds(option: &'static str, value: f64) -> Result<f64, InvalidMediaOption> {
+            if value.is_finite() && value >= 0.0 {
+                Ok(value)
+            } else {
+                Err(InvalidMediaOption{ option, reason: "must be a finite, non-negative number of seconds" })
+            }
+        }
+        fn no_nul(option: &'static str, value: &str) -> Result<(), InvalidMediaOption> {
+            if value.contains('\0') {
+                Err(InvalidMediaOption{ option, reason: "contains a null byte" })
+            } else {
+                Ok(())
+            }
+        }
+
+        let mut options = Vec::new();
+
+        if let Some(t) = self.start_time {
+            options.push(format!(":start-time={}", seconds("start-time", t)?));
+        }
+        if let Some(t) = self.stop_time {
+            let t = seconds("stop-time", t)?;
+            if self.start_time.map(|start| t <= start).unwrap_or(false) {
+                return Err(InvalidMediaOption{ option: "stop-time", reason: "must be after start-time" });
+            }
+            options.push(format!(":stop-time={}", t));
+        }
+        if let Some(t) = self.run_time {
+            options.push(format!(":run-time={}", seconds("run-time", t)?));
+        }
+        if let Some(n) = self.input_repeat {
+            if n > i32::MAX as u32 {
+                return Err(InvalidMediaOption{ option: "input-repeat", reason: "out of range" });
+            }
+            options.push(format!(":input-repeat={}", n));
+        }
+        if let Some(ms) = self.network_caching {
+            options.push(format!(":network-caching={}", ms));
+        }
+        if let Some(ms) = self.file_caching {
+            options.push(format!(":file-caching={}", ms));
+        }
+        if let Some(ref path) = self.sub_file {
+            let path = path.to_str().ok_or(InvalidMediaOption{ option: "sub-file", reason: "path is not valid UTF-8" })?;
+            no_nul("sub-file", path)?;
+            options.push(format!(":sub-file={}", path));
+        }
+        if self.no_audio {
+            options.push(":no-audio".to_owned());
+        }
+        if self.no_video {
+            options.push(":no-video".to_owned());
+        }
+        if self.no_spu {
+            options.push(":no-spu".to_owned());
+        }
+        if let Some(ref chain) = self.sout {
+            if chain.is_empty() {
+                return Err(InvalidMediaOption{ option: "sout", reason: "empty stream output chain" });
+            }
+            no_nul("sout", chain)?;
+            options.push(format!(":sout={}", chain));
+        }
+        for option in &self.extra {
+            if option.is_empty() {
+                return Err(InvalidMediaOption{ option: "option", reason: "empty option" });
+            }
+            no_nul("option", option)?;
+            if option.starts_with(':') {
+                options.push(option.clone());
+            } else {
+                options.push(format!(":{}", option));
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_options() {
+        let options = MediaOptions::new()
+            .start_time(10.0)
+            .stop_time(20.5)
+            .run_time(5.0)
+            .input_repeat(2)
+            .network_caching(300)
+            .file_caching(100)
+            .sub_file("/tmp/movie.srt")
+            .no_audio()
+            .no_video()
+            .no_spu()
+            .sout("#std{access=file,dst=out.ts}")
+            .option("avcodec-hw=none")
+            .option(":demux=avformat");
+        assert_eq!(options.to_options().unwrap(), vec![
+            ":start-time=10",
+            ":stop-time=20.5",
+            ":run-time=5",
+            ":input-repeat=2",
+            ":network-caching=300",
+            ":file-caching=100",
+            ":sub-file=/tmp/movie.srt",
+            ":no-audio",
+            ":no-video",
+            ":no-spu",
+            ":sout=#std{access=file,dst=out.ts}",
+            ":avcodec-hw=none",
+            ":demux=avformat",
+        ]);
+        assert!(MediaOptions::new().to_options().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let rejected = |options: MediaOptions| options.to_options().unwrap_err().option;
+        assert_eq!(rejected(MediaOptions::new().start_time(-1.0)), "start-time");
+        assert_eq!(rejected(MediaOptions::new().start_time(f64::NAN)), "start-time");
+        assert_eq!(rejected(MediaOptions::new().stop_time(f64::INFINITY)), "stop-time");
+        assert_eq!(rejected(MediaOptions::new().start_time(10.0).stop_time(10.0)), "stop-time");
+        assert_eq!(rejected(MediaOptions::new().run_time(-0.5)), "run-time");
+        assert_eq!(rejected(MediaOptions::new().input_repeat(u32::MAX)), "input-repeat");
+        assert_eq!(rejected(MediaOptions::new().sub_file("a\0b.srt")), "sub-file");
+        assert_eq!(rejected(MediaOptions::new().sout("")), "sout");
+        assert_eq!(rejected(MediaOptions::new().sout("#std\0")), "sout");
+        assert_eq!(rejected(MediaOptions::new().option("")), "option");
+        assert_eq!(rejected(MediaOptions::new().option("no-audio\0")), "option");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_non_utf8_sub_file() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let options = MediaOptions::new().sub_file(OsStr::from_bytes(b"/tmp/\xff.srt"));
+        assert_eq!(options.to_options().unwrap_err().option, "sub-file");
+    }
+}
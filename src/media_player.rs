@@ -6,13 +6,22 @@ use crate::sys;
 use crate::Instance;
 use crate::Media;
 use crate::EventManager;
-use libc::{c_void, c_uint};
+use crate::Equalizer;
+use libc::c_void;
 use crate::enums::{State, Position};
-use std::mem::transmute;
+use std::sync::{Arc, Mutex};
+use crate::video_sink::{self, VideoSink, VideoSinkSlot};
+use crate::audio::AudioOutputDevice;
+use crate::audio_sink::{self, AudioSink, AudioHandler, AudioHandlerSlot, AudioCallbacks};
+use crate::tools::{to_cstr, from_cstr};
 
 /// A LibVLC media player plays one media (usually in a custom drawable).
 pub struct MediaPlayer {
     pub(crate) ptr: *mut sys::libvlc_media_player_t,
+    // Referenced by libvlc as opaque pointer of the video callbacks, boxed for a stable address
+    video_sink: Box<VideoSinkSlot>,
+    // Referenced by libvlc as opaque pointer of the audio callbacks, boxed for a stable address
+    audio_handler: Box<AudioHandlerSlot>,
 }
 
 unsafe impl Send for MediaPlayer {}
@@ -26,7 +35,11 @@ impl MediaPlayer {
             if p.is_null() {
                 return None;
             }
-            Some(MediaPlayer{ptr: p})
+            Some(MediaPlayer{
+                ptr: p,
+                video_sink: Box::new(Mutex::new(None)),
+                audio_handler: Box::new(Mutex::new(None)),
+            })
         }
     }
 
@@ -87,6 +100,14 @@ impl MediaPlayer {
         unsafe{ (sys::get_vlc_dll().libvlc_media_player_stop)(self.ptr) };
     }
 
+    /// Set callbacks receiving the decoded audio instead of playing it.
+    /// The samples are always signed 16-bit native-endian, 44100 Hz stereo (interleaved),
+    /// use `set_audio_sink()` to choose the format.
+    ///
+    /// The callbacks are owned by the player: they are released when the player is dropped,
+    /// when they are replaced by another call to `set_callbacks()` / `set_audio_sink()` or
+    /// removed with `unset_audio_callbacks()`. An audio output that is already running keeps
+    /// using the previous callbacks until it is closed.
     pub fn set_callbacks<F>(
         &self,
         play: F,
@@ -96,26 +117,129 @@ impl MediaPlayer {
         drain: Option<Box<dyn Fn() + Send + 'static>>)
         where F: Fn(*const c_void, u32, i64) + Send + 'static,
     {
-        let flag_pause = pause.is_some();
-        let flag_resume = resume.is_some();
-        let flag_flush = flush.is_some();
-        let flag_drain = drain.is_some();
-
-        let data = AudioCallbacksData {
-            play: Box::new(play), pause: pause, resume: resume,
-            flush: flush, drain: drain,
+        self.set_audio_handler(AudioHandler::Callbacks(AudioCallbacks {
+            play: Box::new(play), pause, resume, flush, drain,
+        }));
+    }
+
+    /// Play the audio into `sink` instead of the audio output device.
+    ///
+    /// Replaces the previous sink or callbacks, an audio output that is already running keeps
+    /// using the previous sink until it is closed. Must be called before playback to take effect.
+    pub fn set_audio_sink<S: AudioSink>(&self, sink: S) {
+        self.set_audio_handler(AudioHandler::Sink(Box::new(sink)));
+    }
+
+    /// Remove the callbacks or sink set with `set_callbacks()` / `set_audio_sink()`
+    /// and play the audio with the audio output module `output` again, e.g. "pulse".
+    /// libvlc can not restore its automatic module selection, see `Instance::audio_outputs()`.
+    pub fn unset_audio_callbacks(&self, output: &str) -> Result<(), ()> {
+        self.audio_handler.lock().unwrap().take();
+        self.set_audio_output(output)
+    }
+
+    /// Select the audio output module, e.g. "alsa" or "pulse".
+    /// Takes effect when the next media is played, and unsets the audio callbacks.
+    pub fn set_audio_output(&self, name: &str) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_audio_output_set)(self.ptr, to_cstr(name).as_ptr()) == 0 {
+                Ok(())
+            }else{
+                Err(())
+            }
+        }
+    }
+
+    /// Returns the devices of the current audio output.
+    /// Some modules only list their devices once the audio output is started.
+    pub fn audio_devices(&self) -> Vec<AudioOutputDevice> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_audio_output_device_enum)(self.ptr);
+            AudioOutputDevice::from_list(p)
+        }
+    }
+
+    /// Select the audio output device `device_id` of the module `output`,
+    /// or of the current audio output if `output` is None.
+    /// libvlc does not report errors, check `audio_device()` to see if the device was changed.
+    pub fn set_audio_device(&self, output: Option<&str>, device_id: &str) {
+        let output = output.map(to_cstr);
+        unsafe{
+            (sys::get_vlc_dll().libvlc_audio_output_device_set)(
+                self.ptr,
+                output.as_ref().map_or(::std::ptr::null(), |o| o.as_ptr()),
+                to_cstr(device_id).as_ptr());
+        }
+    }
+
+    /// Returns the identifier of the current audio output device, if any.
+    pub fn audio_device(&self) -> Option<String> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_audio_output_device_get)(self.ptr);
+            let s = from_cstr(p);
+            if !p.is_null() { (sys::get_vlc_dll().libvlc_free)(p as *mut c_void); }
+            s
+        }
+    }
+
+    /// Apply the equalizer settings, or disable the equalizer if `equalizer` is None.
+    /// The settings are copied, `equalizer` can be dropped or modified and applied again afterwards.
+    pub fn set_equalizer(&self, equalizer: Option<&Equalizer>) -> Result<(), ()> {
+        let p = equalizer.map_or(::std::ptr::null_mut(), |eq| eq.ptr);
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_media_player_set_equalizer)(self.ptr, p) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+
+    fn set_audio_handler(&self, handler: AudioHandler) {
+        // A NULL callback lets libvlc skip the call, or apply volume and mute in software
+        let (pause, resume, flush, drain, volume) = match handler {
+            AudioHandler::Sink(ref sink) => (true, true, true, true, sink.handles_volume()),
+            AudioHandler::Callbacks(ref cb) =>
+                (cb.pause.is_some(), cb.resume.is_some(), cb.flush.is_some(), cb.drain.is_some(), false),
         };
-        let data = Box::into_raw(Box::new(data));
+        *self.audio_handler.lock().unwrap() = Some(Arc::new(Mutex::new(handler)));
 
+        let slot = &*self.audio_handler as *const AudioHandlerSlot as *mut c_void;
         unsafe{
             (sys::get_vlc_dll().libvlc_audio_set_callbacks)(
                 self.ptr,
-                Some(audio_cb_play),
-                if flag_pause {Some(audio_cb_pause)} else {None},
-                if flag_resume {Some(audio_cb_resume)} else {None},
-                if flag_flush {Some(audio_cb_flush)} else {None},
-                if flag_drain {Some(audio_cb_drain)} else {None},
-                data as *mut c_void);
+                Some(audio_sink::audio_play),
+                if pause { Some(audio_sink::audio_pause) }else{ None },
+                if resume { Some(audio_sink::audio_resume) }else{ None },
+                if flush { Some(audio_sink::audio_flush) }else{ None },
+                if drain { Some(audio_sink::audio_drain) }else{ None },
+                slot);
+            (sys::get_vlc_dll().libvlc_audio_set_volume_callback)(
+                self.ptr,
+                if volume { Some(audio_sink::audio_set_volume) }else{ None });
+            (sys::get_vlc_dll().libvlc_audio_set_format_callbacks)(
+                self.ptr,
+                Some(audio_sink::audio_setup),
+                Some(audio_sink::audio_cleanup));
+        }
+    }
+
+    /// Render the video into `sink` instead of a window.
+    ///
+    /// Replaces the previous sink, a video output that is already running keeps using
+    /// the previous sink until it is closed. Must be called before playback to take effect.
+    pub fn set_video_sink<S: VideoSink>(&self, sink: S) {
+        let sink: Box<dyn VideoSink> = Box::new(sink);
+        *self.video_sink.lock().unwrap() = Some(Arc::new(Mutex::new(sink)));
+
+        let slot = &*self.video_sink as *const VideoSinkSlot as *mut c_void;
+        unsafe{
+            (sys::get_vlc_dll().libvlc_video_set_callbacks)(
+                self.ptr,
+                Some(video_sink::video_sink_lock),
+                Some(video_sink::video_sink_unlock),
+                Some(video_sink::video_sink_display),
+                slot);
+            (sys::get_vlc_dll().libvlc_video_set_format_callbacks)(
+                self.ptr,
+                Some(video_sink::video_sink_format),
+                Some(video_sink::video_sink_cleanup));
         }
     }
 
@@ -235,7 +359,52 @@ impl MediaPlayer {
     pub fn title_count(&self) -> Option<i32> {
         unsafe{
             let t = (sys::get_vlc_dll().libvlc_media_player_get_title_count)(self.ptr);
-            if t == -1 { Some(t) } else { None }
+            if t == -1 { None }else{ Some(t) }
+        }
+    }
+
+    /// Get the titles of the current media, with their durations.
+    pub fn titles(&self) -> Vec<TitleInfo> {
+        unsafe{
+            let mut p_titles: *mut *mut sys::libvlc_title_description_t = ::std::ptr::null_mut();
+            let n = (sys::get_vlc_dll().libvlc_media_player_get_full_title_descriptions)(self.ptr, &mut p_titles);
+            if n <= 0 || p_titles.is_null() {
+                return Vec::new();
+            }
+
+            let titles = (0..n as usize).map(|i| {
+                let t = &**p_titles.add(i);
+                TitleInfo{
+                    duration: t.i_duration,
+                    name: from_cstr(t.psz_name),
+                    is_menu: t.i_flags & sys::libvlc_title_menu != 0,
+                    is_interactive: t.i_flags & sys::libvlc_title_interactive != 0,
+                }
+            }).collect();
+            (sys::get_vlc_dll().libvlc_title_descriptions_release)(p_titles, n as u32);
+            titles
+        }
+    }
+
+    /// Get the chapters of a title, -1 for the current title, with their time offsets and durations.
+    pub fn chapters(&self, title: i32) -> Vec<ChapterInfo> {
+        unsafe{
+            let mut p_chapters: *mut *mut sys::libvlc_chapter_description_t = ::std::ptr::null_mut();
+            let n = (sys::get_vlc_dll().libvlc_media_player_get_full_chapter_descriptions)(self.ptr, title, &mut p_chapters);
+            if n <= 0 || p_chapters.is_null() {
+                return Vec::new();
+            }
+
+            let chapters = (0..n as usize).map(|i| {
+                let c = &**p_chapters.add(i);
+                ChapterInfo{
+                    time_offset: c.i_time_offset,
+                    duration: c.i_duration,
+                    name: from_cstr(c.psz_name),
+                }
+            }).collect();
+            (sys::get_vlc_dll().libvlc_chapter_descriptions_release)(p_chapters, n as u32);
+            chapters
         }
     }
 
@@ -326,39 +495,26 @@ impl Drop for MediaPlayer {
     }
 }
 
-// For audio_set_callbacks
-struct AudioCallbacksData {
-    play: Box<dyn Fn(*const c_void, u32, i64) + Send + 'static>,
-    pause: Option<Box<dyn Fn(i64) + Send + 'static>>,
-    resume: Option<Box<dyn Fn(i64) + Send + 'static>>,
-    flush: Option<Box<dyn Fn(i64) + Send + 'static>>,
-    drain: Option<Box<dyn Fn() + Send + 'static>>,
-}
-
-unsafe extern "C" fn audio_cb_play(
-    data: *mut c_void, samples: *const c_void, count: c_uint, pts: i64) {
-    let data: &AudioCallbacksData = transmute(data as *mut AudioCallbacksData);
-    (data.play)(samples, count, pts);
-}
-
-unsafe extern "C" fn audio_cb_pause(data: *mut c_void, pts: i64) {
-    let data: &AudioCallbacksData = transmute(data as *mut AudioCallbacksData);
-    (data.pause.as_ref().unwrap())(pts);
-}
-
-unsafe extern "C" fn audio_cb_resume(data: *mut c_void, pts: i64) {
-    let data: &AudioCallbacksData = transmute(data as *mut AudioCallbacksData);
-    (data.resume.as_ref().unwrap())(pts);
-}
-
-unsafe extern "C" fn audio_cb_flush(data: *mut c_void, pts: i64) {
-    let data: &AudioCallbacksData = transmute(data as *mut AudioCallbacksData);
-    (data.flush.as_ref().unwrap())(pts);
+/// Description of a title, returned by `MediaPlayer::titles()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TitleInfo {
+    /// Duration (in ms)
+    pub duration: i64,
+    pub name: Option<String>,
+    /// The title is a menu (e.g. a DVD menu)
+    pub is_menu: bool,
+    /// The title is interactive
+    pub is_interactive: bool,
 }
 
-unsafe extern "C" fn audio_cb_drain(data: *mut c_void) {
-    let data: &AudioCallbacksData = transmute(data as *mut AudioCallbacksData);
-    (data.drain.as_ref().unwrap())();
+/// Description of a chapter, returned by `MediaPlayer::chapters()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ChapterInfo {
+    /// Start of the chapter in the title (in ms)
+    pub time_offset: i64,
+    /// Duration (in ms)
+    pub duration: i64,
+    pub name: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -367,3 +523,19 @@ pub struct TrackDescription {
     pub name: Option<String>,
 }
 
+impl TrackDescription {
+    // Takes ownership of the list and releases it
+    pub(crate) unsafe fn from_list(p0: *mut sys::libvlc_track_description_t) -> Option<Vec<TrackDescription>> {
+        if p0.is_null() { return None; }
+        let mut td = Vec::new();
+        let mut p = p0;
+
+        while !p.is_null() {
+            td.push(TrackDescription{ id: (*p).i_id, name: from_cstr((*p).psz_name) });
+            p = (*p).p_next;
+        }
+        (sys::get_vlc_dll().libvlc_track_description_list_release)(p0);
+        Some(td)
+    }
+}
+
@@ -0,0 +1,200 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::Media;
+use std::time::{Duration, Instant};
+
+/// Playback statistics of a media, see `Media::stats()`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct MediaStats {
+    /* Input */
+    pub read_bytes: i32,
+    pub input_bitrate: f32,
+    /* Demux */
+    pub demux_read_bytes: i32,
+    pub demux_bitrate: f32,
+    pub demux_corrupted: i32,
+    pub demux_discontinuity: i32,
+    /* Decoders */
+    pub decoded_video: i32,
+    pub decoded_audio: i32,
+    /* Video Output */
+    pub displayed_pictures: i32,
+    pub lost_pictures: i32,
+    /* Audio output */
+    pub played_abuffers: i32,
+    pub lost_abuffers: i32,
+    /* Stream output */
+    pub sent_packets: i32,
+    pub sent_bytes: i32,
+    pub send_bitrate: f32,
+}
+
+impl From<sys::libvlc_media_stats_t> for MediaStats {
+    fn from(s: sys::libvlc_media_stats_t) -> MediaStats {
+        MediaStats {
+            read_bytes:          s.i_read_bytes,
+            input_bitrate:       s.f_input_bitrate,
+            demux_read_bytes:    s.i_demux_read_bytes,
+            demux_bitrate:       s.f_demux_bitrate,
+            demux_corrupted:     s.i_demux_corrupted,
+            demux_discontinuity: s.i_demux_discontinuity,
+            decoded_video:       s.i_decoded_video,
+            decoded_audio:       s.i_decoded_audio,
+            displayed_pictures:  s.i_displayed_pictures,
+            lost_pictures:       s.i_lost_pictures,
+            played_abuffers:     s.i_played_abuffers,
+            lost_abuffers:       s.i_lost_abuffers,
+            sent_packets:        s.i_sent_packets,
+            sent_bytes:          s.i_sent_bytes,
+            send_bitrate:        s.f_send_bitrate,
+        }
+    }
+}
+
+/// Difference between two `MediaStats` samples.
+///
+/// Counters are the increase since the previous sample, the `*_rate` methods
+/// normalize them to one second.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StatsDelta {
+    /// Time elapsed between the two samples
+    pub elapsed: Duration,
+    /// The most recent sample
+    pub current: MediaStats,
+    pub read_bytes: i64,
+    pub demux_read_bytes: i64,
+    pub demux_corrupted: i64,
+    pub demux_discontinuity: i64,
+    pub decoded_video: i64,
+    pub decoded_audio: i64,
+    pub displayed_pictures: i64,
+    pub lost_pictures: i64,
+    pub played_abuffers: i64,
+    pub lost_abuffers: i64,
+    pub sent_packets: i64,
+    pub sent_bytes: i64,
+}
+
+impl StatsDelta {
+    fn new(previous: &MediaStats, current: &MediaStats, elapsed: Duration) -> StatsDelta {
+        // Counters are reset when the input restarts, never report negative deltas
+        fn delta(previous: i32, current: i32) -> i64 {
+            (current as i64 - previous as i64).max(0)
+        }
+
+        StatsDelta {
+            elapsed,
+            current: *current,
+            read_bytes:          delta(previous.read_bytes, current.read_bytes),
+            demux_read_bytes:    delta(previous.demux_read_bytes, current.demux_read_bytes),
+            demux_corrupted:     delta(previous.demux_corrupted, current.demux_corrupted),
+            demux_discontinuity: delta(previous.demux_discontinuity, current.demux_discontinuity),
+            decoded_video:       delta(previous.decoded_video, current.decoded_video),
+            decoded_audio:       delta(previous.decoded_audio, current.decoded_audio),
+            displayed_pictures:  delta(previous.displayed_pictures, current.displayed_pictures),
+            lost_pictures:       delta(previous.lost_pictures, current.lost_pictures),
+            played_abuffers:     delta(previous.played_abuffers, current.played_abuffers),
+            lost_abuffers:       delta(previous.lost_abuffers, current.lost_abuffers),
+            sent_packets:        delta(previous.sent_packets, current.sent_packets),
+            sent_bytes:          delta(previous.sent_bytes, current.sent_bytes),
+        }
+    }
+
+    fn per_second(&self, value: i64) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 { value as f64 / secs } else { 0.0 }
+    }
+
+    /// Bytes read from the input per second.
+    pub fn read_rate(&self) -> f64 {
+        self.per_second(self.read_bytes)
+    }
+
+    /// Pictures displayed per second.
+    pub fn displayed_rate(&self) -> f64 {
+        self.per_second(self.displayed_pictures)
+    }
+
+    /// Pictures lost (dropped) per second.
+    pub fn lost_picture_rate(&self) -> f64 {
+        self.per_second(self.lost_pictures)
+    }
+
+    /// Audio buffers lost per second.
+    pub fn lost_abuffer_rate(&self) -> f64 {
+        self.per_second(self.lost_abuffers)
+    }
+
+    /// Fraction (0.0 - 1.0) of pictures lost in this interval.
+    pub fn lost_picture_ratio(&self) -> f64 {
+        let total = self.displayed_pictures + self.lost_pictures;
+        if total > 0 { self.lost_pictures as f64 / total as f64 } else { 0.0 }
+    }
+}
+
+/// Polls `Media::stats()` and computes the deltas between samples.
+///
+/// ```no_run
+/// # use vlc_static::*;
+/// # use std::time::Duration;
+/// # let instance = Instance::new().unwrap();
+/// # let md = Media::new_path(&instance, "movie.mkv").unwrap();
+/// let mut sampler = StatsSampler::new(Duration::from_secs(1));
+/// loop {
+///     if let Some(delta) = sampler.poll(&md) {
+///         if delta.lost_picture_ratio() > 0.05 {
+///             println!("dropping frames: {} lost/s", delta.lost_picture_rate());
+///         }
+///     }
+///     std::thread::sleep(Duration::from_millis(100));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatsSampler {
+    interval: Duration,
+    last: Option<(Instant, MediaStats)>,
+}
+
+impl StatsSampler {
+    /// Create a sampler that produces at most one delta per `interval`.
+    pub fn new(interval: Duration) -> StatsSampler {
+        StatsSampler { interval, last: None }
+    }
+
+    /// Sampling interval
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Take a sample if the interval has elapsed since the previous one.
+    /// Returns the delta to the previous sample, or None if it is too early,
+    /// this is the first sample or the statistics are unavailable.
+    pub fn poll(&mut self, media: &Media) -> Option<StatsDelta> {
+        let now = Instant::now();
+        if let Some((t, _)) = self.last {
+            if now.duration_since(t) < self.interval {
+                return None;
+            }
+        }
+        self.sample_at(media, now)
+    }
+
+    /// Take a sample now, regardless of the interval.
+    pub fn sample(&mut self, media: &Media) -> Option<StatsDelta> {
+        self.sample_at(media, Instant::now())
+    }
+
+    /// Forget the previous sample, e.g. after the media was changed.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    fn sample_at(&mut self, media: &Media, now: Instant) -> Option<StatsDelta> {
+        let current = media.stats()?;
+        let previous = self.last.replace((now, current));
+        previous.map(|(t, stats)| StatsDelta::new(&stats, &current, now.duration_since(t)))
+    }
+}
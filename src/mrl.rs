@@ -0,0 +1,285 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A media resource locator, e.g. `file:///home/user/movie%20one.mkv` or `http://host/stream`.
+///
+/// The path and the query are stored as they appear in the URI (percent-encoded), so a parsed
+/// MRL is formatted back unchanged. Paths given as raw bytes (`new()`, `from_path()`) are
+/// percent-encoded, so paths that are not valid UTF-8 survive a round trip.
+/// Input options (`:option=value`) are kept separately, they are not part of the
+/// URI and are applied with `Media::add_option()` by `Media::new_mrl()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Mrl {
+    scheme: String,
+    host: String,
+    // Percent-encoded
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+    options: Vec<String>,
+}
+
+impl Mrl {
+    /// Create an MRL from a scheme, a host and a decoded path.
+    pub fn new(scheme: &str, host: &str, path: &[u8]) -> Mrl {
+        Mrl {
+            scheme: scheme.to_owned(),
+            host: host.to_owned(),
+            path: percent_encode(path),
+            query: None,
+            fragment: None,
+            options: Vec::new(),
+        }
+    }
+
+    /// Create a `file://` MRL from a path.
+    /// Relative paths are resolved against the current directory.
+    pub fn from_path<T: AsRef<Path>>(path: T) -> Option<Mrl> {
+        let path = path.as_ref();
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir().ok()?.join(path)
+        };
+
+        #[cfg(unix)]
+        let bytes = {
+            use std::os::unix::ffi::OsStrExt;
+            path.as_os_str().as_bytes().to_vec()
+        };
+        // Windows paths are UTF-16, libvlc expects UTF-8 URIs
+        #[cfg(not(unix))]
+        let bytes = {
+            let mut s = path.to_str()?.replace('\\', "/");
+            if !s.starts_with('/') {
+                s.insert(0, '/');
+            }
+            s.into_bytes()
+        };
+
+        Some(Mrl::new("file", "", &bytes))
+    }
+
+    /// Parse an MRL of the form `scheme://host/path?query#fragment :option1 :option2`.
+    /// Returns None if there is no `scheme://` prefix.
+    pub fn parse(mrl: &str) -> Option<Mrl> {
+        let mut parts = mrl.split(" :");
+        let uri = parts.next()?.trim();
+        let options = parts.map(|o| format!(":{}", o.trim_end())).collect();
+
+        let scheme_end = uri.find("://")?;
+        let scheme = &uri[..scheme_end];
+        if scheme.is_empty() {
+            return None;
+        }
+        let rest = &uri[scheme_end + 3..];
+
+        let (rest, fragment) = match rest.find('#') {
+            Some(i) => (&rest[..i], Some(rest[i + 1..].to_owned())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(i) => (&rest[..i], Some(rest[i + 1..].to_owned())),
+            None => (rest, None),
+        };
+        let (host, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, ""),
+        };
+
+        Some(Mrl {
+            scheme: scheme.to_owned(),
+            host: host.to_owned(),
+            path: path.to_owned(),
+            query,
+            fragment,
+            options,
+        })
+    }
+
+    /// Access (and optionally demux) part, e.g. `file`, `http` or `dvd`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Host (authority) part, empty for local files.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Decoded path as raw bytes.
+    pub fn path_bytes(&self) -> Vec<u8> {
+        percent_decode(&self.path)
+    }
+
+    /// Decoded path, lossily converted to UTF-8.
+    pub fn path(&self) -> String {
+        String::from_utf8_lossy(&self.path_bytes()).into_owned()
+    }
+
+    /// Path as it appears in the URI, percent-encoded.
+    pub fn encoded_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Query after the `?`, as it appears in the URI.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn set_query(&mut self, query: Option<&str>) {
+        self.query = query.map(|s| s.to_owned());
+    }
+
+    /// Title / chapter selection after the `#`, e.g. `1:2` for DVDs.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    pub fn set_fragment(&mut self, fragment: Option<&str>) {
+        self.fragment = fragment.map(|s| s.to_owned());
+    }
+
+    /// Input options, in the `:option=value` form.
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Add an input option, a leading `:` is added if missing.
+    pub fn add_option(&mut self, option: &str) {
+        if option.starts_with(':') {
+            self.options.push(option.to_owned());
+        } else {
+            self.options.push(format!(":{}", option));
+        }
+    }
+
+    /// Convert a local `file://` MRL back into a path.
+    pub fn to_path(&self) -> Option<PathBuf> {
+        if self.scheme != "file" || !(self.host.is_empty() || self.host == "localhost") {
+            return None;
+        }
+        let path = self.path_bytes();
+
+        #[cfg(unix)]
+        {
+            use std::ffi::OsStr;
+            use std::os::unix::ffi::OsStrExt;
+            Some(PathBuf::from(OsStr::from_bytes(&path)))
+        }
+        #[cfg(not(unix))]
+        {
+            let s = ::std::str::from_utf8(&path).ok()?;
+            // "/C:/dir" -> "C:\dir"
+            let s = if s.len() > 2 && s.as_bytes()[2] == b':' { &s[1..] } else { s };
+            Some(PathBuf::from(s.replace('/', "\\")))
+        }
+    }
+}
+
+/// Formats the URI part (without options).
+impl fmt::Display for Mrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}://{}{}", self.scheme, self.host, self.path)?;
+        if let Some(ref query) = self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(ref fragment) = self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+fn percent_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len());
+    for (i, b) in bytes.iter().enumerate() {
+        match *b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => s.push(*b as char),
+            // Keep drive letters ("/C:/...") readable
+            b':' if i == 2 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() => s.push(':'),
+            _ => s.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    s
+}
+
+fn percent_decode(s: &str) -> Vec<u8> {
+    fn hex(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(h), Some(l)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push(h << 4 | l);
+                i += 3;
+                continue;
+            }
+        }
+        // Invalid escapes are kept as they are
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keeps_query_and_escapes() {
+        let mrl = Mrl::parse("http://h/s%20t?token=a%2Fb&x=1#2 :network-caching=300").unwrap();
+        assert_eq!(mrl.scheme(), "http");
+        assert_eq!(mrl.host(), "h");
+        assert_eq!(mrl.encoded_path(), "/s%20t");
+        assert_eq!(mrl.path(), "/s t");
+        assert_eq!(mrl.query(), Some("token=a%2Fb&x=1"));
+        assert_eq!(mrl.fragment(), Some("2"));
+        assert_eq!(mrl.options(), &[":network-caching=300".to_owned()]);
+        assert_eq!(mrl.to_string(), "http://h/s%20t?token=a%2Fb&x=1#2");
+    }
+
+    #[test]
+    fn parse_to_string_round_trip() {
+        for uri in &["http://h/s?token=a%2Fb", "file:///home/user/a%2Fb.mkv", "dvd:///dev/sr0#1:2", "rtsp://cam:554"] {
+            assert_eq!(&Mrl::parse(uri).unwrap().to_string(), uri);
+        }
+        assert_eq!(Mrl::parse("no-scheme"), None);
+    }
+
+    #[test]
+    fn new_encodes_raw_path() {
+        let mrl = Mrl::new("file", "", b"/a b/c?d#e%f");
+        assert_eq!(mrl.to_string(), "file:///a%20b/c%3Fd%23e%25f");
+        assert_eq!(mrl.path_bytes(), b"/a b/c?d#e%f".to_vec());
+        assert_eq!(Mrl::parse(&mrl.to_string()).unwrap().path_bytes(), mrl.path_bytes());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_round_trip() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/tmp/movie one?\xff.mkv"));
+        let mrl = Mrl::from_path(path).unwrap();
+        assert_eq!(mrl.to_string(), "file:///tmp/movie%20one%3F%FF.mkv");
+        assert_eq!(mrl.to_path().unwrap(), path);
+        assert_eq!(Mrl::parse(&mrl.to_string()).unwrap().to_path().unwrap(), path);
+        assert_eq!(Mrl::parse("http://h/x").unwrap().to_path(), None);
+    }
+}
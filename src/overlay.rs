@@ -0,0 +1,311 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::MediaPlayer;
+use crate::enums::Position;
+use crate::tools::{to_cstr, path_to_cstr};
+use std::ffi::CString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Error returned when an overlay can not be applied.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidOverlay {
+    pub reason: &'static str,
+}
+
+impl fmt::Display for InvalidOverlay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid overlay: {}", self.reason)
+    }
+}
+
+impl ::std::error::Error for InvalidOverlay {}
+
+// The overlay filters take a bit mask (1 left, 2 right, 4 top, 8 bottom)
+// rather than a libvlc_position_t, -1 places the overlay at its x/y offset.
+fn position_mask(position: Position) -> i32 {
+    match position {
+        Position::Disable => -1,
+        Position::Center => 0,
+        Position::Left => 1,
+        Position::Right => 2,
+        Position::Top => 4,
+        Position::TopLeft => 4 | 1,
+        Position::TopRight => 4 | 2,
+        Position::Bottom => 8,
+        Position::BottomLeft => 8 | 1,
+        Position::BottomRight => 8 | 2,
+    }
+}
+
+/// Text overlay, applied with `MediaPlayerOverlayEx::set_marquee()`.
+///
+/// The text may contain VLC's time format sequences (`%H:%M:%S`, ...)
+/// and meta variables (`$t` for the title, `$T` for the time, ...).
+///
+/// ```no_run
+/// # use vlc_static::*;
+/// # let instance = Instance::new().unwrap();
+/// # let mdp = MediaPlayer::new(&instance).unwrap();
+/// let marquee = Marquee::new("Closing at 18:00 - %H:%M")
+///     .color(0xff, 0xff, 0x00)
+///     .position(Position::Bottom)
+///     .refresh(1000);
+/// mdp.set_marquee(Some(&marquee)).unwrap();
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Marquee {
+    text: String,
+    color: (u8, u8, u8),
+    opacity: u8,
+    position: Position,
+    refresh: u32,
+    size: u32,
+    timeout: u32,
+    offset: (i32, i32),
+}
+
+impl Marquee {
+    /// White, opaque text in the top left corner, with the default font size.
+    pub fn new(text: &str) -> Marquee {
+        Marquee{
+            text: text.to_owned(),
+            color: (0xff, 0xff, 0xff),
+            opacity: 255,
+            position: Position::Disable,
+            refresh: 1000,
+            size: 0,
+            timeout: 0,
+            offset: (0, 0),
+        }
+    }
+
+    /// Text color.
+    pub fn color(mut self, r: u8, g: u8, b: u8) -> Self {
+        self.color = (r, g, b);
+        self
+    }
+
+    /// Opacity, 0 is transparent and 255 opaque.
+    pub fn opacity(mut self, opacity: u8) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Position of the text, `Position::Disable` places it at the offset from the top left corner.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Interval (in ms) at which time format sequences are updated.
+    pub fn refresh(mut self, ms: u32) -> Self {
+        self.refresh = ms;
+        self
+    }
+
+    /// Font size (in pixels), 0 uses the default size.
+    pub fn size(mut self, pixels: u32) -> Self {
+        self.size = pixels;
+        self
+    }
+
+    /// Hide the text after the given duration (in ms), 0 shows it forever.
+    pub fn timeout(mut self, ms: u32) -> Self {
+        self.timeout = ms;
+        self
+    }
+
+    /// Offset (in pixels) from the position.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+}
+
+/// One image of a `Logo`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LogoFrame {
+    pub path: PathBuf,
+    /// Display duration (in ms), None uses the delay of the logo.
+    pub delay: Option<u32>,
+    /// Opacity, None uses the opacity of the logo.
+    pub alpha: Option<u8>,
+}
+
+impl LogoFrame {
+    pub fn new<P: AsRef<Path>>(path: P) -> LogoFrame {
+        LogoFrame{ path: path.as_ref().to_path_buf(), delay: None, alpha: None }
+    }
+}
+
+/// Image overlay (watermark), applied with `MediaPlayerOverlayEx::set_logo()`.
+///
+/// A logo with several frames is animated, each frame being shown for its delay.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Logo {
+    frames: Vec<LogoFrame>,
+    offset: (i32, i32),
+    position: Position,
+    opacity: u8,
+    delay: u32,
+    repeat: Option<u32>,
+}
+
+impl Logo {
+    /// A single image in the top left corner.
+    pub fn new<P: AsRef<Path>>(path: P) -> Logo {
+        Logo::animated(vec![LogoFrame::new(path)])
+    }
+
+    /// An animation of the given frames, repeated continuously.
+    pub fn animated(frames: Vec<LogoFrame>) -> Logo {
+        Logo{
+            frames,
+            offset: (0, 0),
+            position: Position::Disable,
+            opacity: 255,
+            delay: 1000,
+            repeat: None,
+        }
+    }
+
+    /// Offset (in pixels) from the position.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Position of the logo, `Position::Disable` places it at the offset from the top left corner.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Opacity, 0 is transparent and 255 opaque.
+    pub fn opacity(mut self, opacity: u8) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Display duration (in ms) of the frames which do not have their own delay.
+    pub fn delay(mut self, ms: u32) -> Self {
+        self.delay = ms;
+        self
+    }
+
+    /// Number of loops of the animation, 0 plays it once and None loops continuously.
+    pub fn repeat(mut self, count: Option<u32>) -> Self {
+        self.repeat = count;
+        self
+    }
+
+    // Render the frames in the `file,delay,alpha;file,...` syntax of the logo filter
+    fn file_list(&self) -> Result<CString, InvalidOverlay> {
+        if self.frames.is_empty() {
+            return Err(InvalidOverlay{ reason: "logo has no frames" });
+        }
+
+        let mut list = Vec::new();
+        for (i, frame) in self.frames.iter().enumerate() {
+            if !frame.path.is_file() {
+                return Err(InvalidOverlay{ reason: "logo file does not exist" });
+            }
+            let path = path_to_cstr(&frame.path)
+                .map_err(|_| InvalidOverlay{ reason: "logo path contains a null byte" })?;
+            let path = path.as_bytes();
+            if path.contains(&b',') || path.contains(&b';') {
+                return Err(InvalidOverlay{ reason: "logo path contains ',' or ';'" });
+            }
+
+            if i > 0 { list.push(b';'); }
+            list.extend_from_slice(path);
+            match (frame.delay, frame.alpha) {
+                (None, None) => {},
+                (Some(delay), None) => list.extend_from_slice(format!(",{}", delay.min(i32::MAX as u32)).as_bytes()),
+                (delay, Some(alpha)) => {
+                    let delay = delay.map_or(-1, |d| d.min(i32::MAX as u32) as i64);
+                    list.extend_from_slice(format!(",{},{}", delay, alpha).as_bytes());
+                },
+            }
+        }
+        // Checked for each path above
+        Ok(CString::new(list).unwrap())
+    }
+}
+
+pub trait MediaPlayerOverlayEx {
+    /// Show the marquee, or hide it if `marquee` is None.
+    /// The marquee is disabled while its options are changed, so a partially applied marquee is never shown.
+    fn set_marquee(&self, marquee: Option<&Marquee>) -> Result<(), InvalidOverlay>;
+    /// Show the logo, or hide it if `logo` is None.
+    /// The files are checked before anything is changed, the logo is disabled while its options are changed.
+    fn set_logo(&self, logo: Option<&Logo>) -> Result<(), InvalidOverlay>;
+}
+
+impl MediaPlayerOverlayEx for MediaPlayer {
+    fn set_marquee(&self, marquee: Option<&Marquee>) -> Result<(), InvalidOverlay> {
+        use crate::sys::libvlc_video_marquee_option_t::*;
+
+        let set_int = |option: sys::libvlc_video_marquee_option_t, value: i32| unsafe{
+            (sys::get_vlc_dll().libvlc_video_set_marquee_int)(self.ptr, option as u32, value);
+        };
+
+        let marquee = match marquee {
+            Some(m) => m,
+            None => { set_int(libvlc_marquee_Enable, 0); return Ok(()); },
+        };
+        if marquee.text.contains('\0') {
+            return Err(InvalidOverlay{ reason: "marquee text contains a null byte" });
+        }
+
+        // Every option is set, so nothing is left over from a previous marquee
+        set_int(libvlc_marquee_Enable, 0);
+        unsafe{
+            (sys::get_vlc_dll().libvlc_video_set_marquee_string)(
+                self.ptr, libvlc_marquee_Text as u32, to_cstr(&marquee.text).as_ptr());
+        }
+        let (r, g, b) = marquee.color;
+        set_int(libvlc_marquee_Color, (r as i32) << 16 | (g as i32) << 8 | b as i32);
+        set_int(libvlc_marquee_Opacity, marquee.opacity as i32);
+        set_int(libvlc_marquee_Position, position_mask(marquee.position));
+        set_int(libvlc_marquee_Refresh, marquee.refresh.min(i32::MAX as u32) as i32);
+        set_int(libvlc_marquee_Size, marquee.size.min(i32::MAX as u32) as i32);
+        set_int(libvlc_marquee_Timeout, marquee.timeout.min(i32::MAX as u32) as i32);
+        set_int(libvlc_marquee_X, marquee.offset.0);
+        set_int(libvlc_marquee_Y, marquee.offset.1);
+        set_int(libvlc_marquee_Enable, 1);
+        Ok(())
+    }
+
+    fn set_logo(&self, logo: Option<&Logo>) -> Result<(), InvalidOverlay> {
+        use crate::sys::libvlc_video_logo_option_t::*;
+
+        let set_int = |option: sys::libvlc_video_logo_option_t, value: i32| unsafe{
+            (sys::get_vlc_dll().libvlc_video_set_logo_int)(self.ptr, option as u32, value);
+        };
+
+        let logo = match logo {
+            Some(l) => l,
+            None => { set_int(libvlc_logo_enable, 0); return Ok(()); },
+        };
+        let files = logo.file_list()?;
+
+        // Every option is set, so nothing is left over from a previous logo
+        set_int(libvlc_logo_enable, 0);
+        unsafe{
+            (sys::get_vlc_dll().libvlc_video_set_logo_string)(self.ptr, libvlc_logo_file as u32, files.as_ptr());
+        }
+        set_int(libvlc_logo_x, logo.offset.0);
+        set_int(libvlc_logo_y, logo.offset.1);
+        set_int(libvlc_logo_delay, logo.delay.min(i32::MAX as u32) as i32);
+        set_int(libvlc_logo_repeat, logo.repeat.map_or(-1, |n| n.min(i32::MAX as u32) as i32));
+        set_int(libvlc_logo_opacity, logo.opacity as i32);
+        set_int(libvlc_logo_position, position_mask(logo.position));
+        set_int(libvlc_logo_enable, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::MediaPlayer;
+use crate::core::wait_for_events;
+use crate::enums::EventType;
+use crate::tools::{path_to_cstr, from_cstr_path};
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// libvlc waits up to 500ms for a picture, then encodes and writes the file
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Decoded snapshot, returned by `MediaPlayerSnapshotEx::snapshot_image()`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SnapshotImage {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, 4 bytes per pixel, rows from top to bottom without padding
+    pub data: Vec<u8>,
+}
+
+/// Error returned when a snapshot can not be taken.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// libvlc refused the snapshot, e.g. there is no video output
+    Failed,
+    /// libvlc did not report the snapshot in time
+    Timeout,
+    /// The path is invalid, or the file could not be created or read
+    Io(io::Error),
+    /// The snapshot is not a PNG image
+    Decode,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SnapshotError::Failed => write!(f, "snapshot failed: no video output"),
+            SnapshotError::Timeout => write!(f, "snapshot failed: timed out"),
+            SnapshotError::Io(ref e) => write!(f, "snapshot failed: {}", e),
+            SnapshotError::Decode => write!(f, "snapshot failed: not a PNG image"),
+        }
+    }
+}
+
+impl ::std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match *self {
+            SnapshotError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> SnapshotError {
+        SnapshotError::Io(e)
+    }
+}
+
+pub trait MediaPlayerSnapshotEx {
+    /// Take a snapshot of the video output `vout` (usually 0) and save it to `path`,
+    /// which may be a file or a directory. Returns the path of the written file.
+    /// If `width` or `height` is 0, it is computed to keep the aspect ratio of the video.
+    ///
+    /// Blocks until libvlc reports the snapshot. Snapshots written elsewhere are ignored,
+    /// but concurrent snapshots into the same directory may be mixed up.
+    fn take_snapshot(&self, vout: u32, path: &Path, width: u32, height: u32) -> Result<PathBuf, SnapshotError>;
+    /// Take a snapshot of the video output `vout` and return it decoded,
+    /// `width` and `height` are handled as in `take_snapshot()`.
+    ///
+    /// The snapshot goes through a temporary file in a private directory, both are removed afterwards.
+    /// Requires the default PNG snapshot format (see the `--snapshot-format` instance argument).
+    fn snapshot_image(&self, vout: u32, width: u32, height: u32) -> Result<SnapshotImage, SnapshotError>;
+}
+
+impl MediaPlayerSnapshotEx for MediaPlayer {
+    fn take_snapshot(&self, vout: u32, path: &Path, width: u32, height: u32) -> Result<PathBuf, SnapshotError> {
+        let cpath = path_to_cstr(path)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains a null byte"))?;
+        let is_dir = path.is_dir();
+        let written = Mutex::new(None);
+
+        // Only accept the snapshot requested here, not one taken by another caller
+        let filter = |pe: &sys::libvlc_event_t| {
+            let p = match unsafe{ from_cstr_path(pe.u.media_player_snapshot_taken.psz_filename) } {
+                Some(p) => p,
+                None => { return false; },
+            };
+            if p != path && !(is_dir && p.parent() == Some(path)) {
+                return false;
+            }
+            if let Ok(mut written) = written.lock() {
+                *written = Some(p);
+            }
+            true
+        };
+
+        let mut started = false;
+        let done = unsafe{
+            let em = (sys::get_vlc_dll().libvlc_media_player_event_manager)(self.ptr);
+            wait_for_events(
+                em,
+                &[EventType::MediaPlayerSnapshotTaken],
+                Some(SNAPSHOT_TIMEOUT),
+                filter,
+                || {
+                    started = (sys::get_vlc_dll().libvlc_video_take_snapshot)(
+                        self.ptr, vout, cpath.as_ptr(), width, height) == 0;
+                    started
+                },
+                || written.lock().map(|w| w.is_some()).unwrap_or(false))
+        };
+
+        if !started {
+            return Err(SnapshotError::Failed);
+        }
+        match written.into_inner().ok().flatten() {
+            Some(p) if done => Ok(p),
+            _ => Err(SnapshotError::Timeout),
+        }
+    }
+
+    fn snapshot_image(&self, vout: u32, width: u32, height: u32) -> Result<SnapshotImage, SnapshotError> {
+        let dir = create_private_dir()?;
+        let tmp = dir.join("snapshot.png");
+        let image = self.take_snapshot(vout, &tmp, width, height).and_then(|p| decode_png(&p));
+
+        let _ = fs::remove_file(&tmp);
+        let _ = fs::remove_dir(&dir);
+        image
+    }
+}
+
+// Create a new directory only accessible by the current user in the temporary directory.
+// Creating the directory fails if the name exists (even as a symlink), so it can not be hijacked.
+fn create_private_dir() -> io::Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    let mut tries = 0;
+    loop {
+        let dir = ::std::env::temp_dir().join(format!(
+            "vlc-rs-snapshot-{}-{}-{}", ::std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed), nanos));
+        match builder.create(&dir) {
+            Ok(()) => { return Ok(dir); },
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists && tries < 100 => { tries += 1; },
+            Err(e) => { return Err(e); },
+        }
+    }
+}
+
+fn decode_png(path: &Path) -> Result<SnapshotImage, SnapshotError> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|_| SnapshotError::Decode)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|_| SnapshotError::Decode)?;
+    buf.truncate(info.buffer_size());
+
+    let data = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf.chunks(3).flat_map(|p| [p[0], p[1], p[2], 0xff]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&p| [p, p, p, 0xff]).collect(),
+        // Expanded by normalize_to_color8()
+        png::ColorType::Indexed => { return Err(SnapshotError::Decode); },
+    };
+    Ok(SnapshotImage{ width: info.width, height: info.height, data })
+}
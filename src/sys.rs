@@ -4,7 +4,7 @@
 
 #![allow(non_camel_case_types, non_upper_case_globals)]
 
-use libc::{c_void, c_int, c_uint, c_char, c_float, uintptr_t, FILE};
+use libc::{c_void, c_int, c_uint, c_char, c_uchar, c_float, size_t, ssize_t, uintptr_t, FILE};
 use libloading_mini::Library;
 
 pub type c_bool = u8;
@@ -58,11 +58,35 @@ pub enum libvlc_media_t {}
 pub use crate::enums::Meta as libvlc_meta_t;
 pub use crate::enums::State as libvlc_state_t;
 
+pub use crate::enums::ParsedStatus as libvlc_media_parsed_status_t;
+
+pub const libvlc_media_parse_local: c_int = 0x00;
+pub const libvlc_media_parse_network: c_int = 0x01;
+pub const libvlc_media_fetch_local: c_int = 0x02;
+pub const libvlc_media_fetch_network: c_int = 0x04;
+pub const libvlc_media_do_interact: c_int = 0x08;
+
 pub const libvlc_media_option_trusted: u32 = 0x2;
 pub const libvlc_media_option_unique: u32 = 0x100;
 
 pub use crate::enums::TrackType as libvlc_track_type_t;
 
+pub type libvlc_media_open_cb = Option<unsafe extern "C" fn(*mut c_void, *mut *mut c_void, *mut u64) -> c_int>;
+pub type libvlc_media_read_cb = Option<unsafe extern "C" fn(*mut c_void, *mut c_uchar, size_t) -> ssize_t>;
+pub type libvlc_media_seek_cb = Option<unsafe extern "C" fn(*mut c_void, u64) -> c_int>;
+pub type libvlc_media_close_cb = Option<unsafe extern "C" fn(*mut c_void)>;
+
+pub use crate::enums::MediaType as libvlc_media_type_t;
+pub use crate::enums::SlaveKind as libvlc_media_slave_type_t;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct libvlc_media_slave_t {
+    pub psz_uri: *mut c_char,
+    pub i_type: libvlc_media_slave_type_t,
+    pub i_priority: c_uint,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct libvlc_media_stats_t {
@@ -118,6 +142,18 @@ pub struct libvlc_audio_track_t {
     pub i_rate: c_uint,
 }
 
+pub use crate::enums::Orientation as libvlc_video_orient_t;
+pub use crate::enums::Projection as libvlc_video_projection_t;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct libvlc_video_viewpoint_t {
+    pub f_yaw: c_float,
+    pub f_pitch: c_float,
+    pub f_roll: c_float,
+    pub f_field_of_view: c_float,
+}
+
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct libvlc_video_track_t {
@@ -127,6 +163,9 @@ pub struct libvlc_video_track_t {
     pub i_sar_den: c_uint,
     pub i_frame_rate_num: c_uint,
     pub i_frame_rate_den: c_uint,
+    pub i_orientation: libvlc_video_orient_t,
+    pub i_projection: libvlc_video_projection_t,
+    pub pose: libvlc_video_viewpoint_t,
 }
 
 #[repr(C)]
@@ -202,6 +241,25 @@ pub struct libvlc_audio_output_device_t {
     pub psz_description: *mut c_char,
 }
 
+pub const libvlc_title_menu: c_uint = 0x01;
+pub const libvlc_title_interactive: c_uint = 0x02;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct libvlc_title_description_t {
+    pub i_duration: i64,
+    pub psz_name: *mut c_char,
+    pub i_flags: c_uint,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct libvlc_chapter_description_t {
+    pub i_time_offset: i64,
+    pub i_duration: i64,
+    pub psz_name: *mut c_char,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct libvlc_rectangle_t {
@@ -238,6 +296,7 @@ pub enum libvlc_navigate_mode_t {
 
 pub use crate::enums::Position as libvlc_position_t;
 pub use crate::enums::VideoAdjustOption as libvlc_video_adjust_option;
+pub use crate::enums::TeletextKey as libvlc_teletext_key_t;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
@@ -277,10 +336,10 @@ pub enum libvlc_audio_output_channel_t {
     libvlc_AudioChannel_Dolbys  =  5
 }
 
-pub type libvlc_video_lock_cb = Option<unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void>;
+pub type libvlc_video_lock_cb = Option<unsafe extern "C" fn(*mut c_void, *mut *mut c_void) -> *mut c_void>;
 pub type libvlc_video_unlock_cb = Option<unsafe extern "C" fn(*mut c_void, *mut c_void, *const *mut c_void)>;
 pub type libvlc_video_display_cb = Option<unsafe extern "C" fn(*mut c_void, *mut c_void)>;
-pub type libvlc_video_format_cb = Option<unsafe extern "C" fn(*mut *mut c_void, *mut c_char, *mut c_uint, *mut c_uint, *mut c_uint, *mut c_uint)>;
+pub type libvlc_video_format_cb = Option<unsafe extern "C" fn(*mut *mut c_void, *mut c_char, *mut c_uint, *mut c_uint, *mut c_uint, *mut c_uint) -> c_uint>;
 pub type libvlc_video_cleanup_cb = Option<unsafe extern "C" fn(*mut c_void)>;
 pub type libvlc_audio_play_cb = Option<unsafe extern "C" fn(*mut c_void, *const c_void, c_uint, i64)>;
 pub type libvlc_audio_pause_cb = Option<unsafe extern "C" fn(*mut c_void, i64)>;
@@ -288,7 +347,7 @@ pub type libvlc_audio_resume_cb = Option<unsafe extern "C" fn(*mut c_void, i64)>
 pub type libvlc_audio_flush_cb = Option<unsafe extern "C" fn(*mut c_void, i64)>;
 pub type libvlc_audio_drain_cb = Option<unsafe extern "C" fn(*mut c_void)>;
 pub type libvlc_audio_set_volume_cb = Option<unsafe extern "C" fn(*mut c_void, c_float, c_bool)>;
-pub type libvlc_audio_setup_cb = Option<unsafe extern "C" fn(*mut *mut c_void, *mut c_char, *mut c_uint, *mut c_uint)>;
+pub type libvlc_audio_setup_cb = Option<unsafe extern "C" fn(*mut *mut c_void, *mut c_char, *mut c_uint, *mut c_uint) -> c_int>;
 pub type libvlc_audio_cleanup_cb = Option<unsafe extern "C" fn(*mut c_void)>;
 
 // From libvlc_events.h
@@ -482,6 +541,7 @@ pub struct VlcDll {
     pub libvlc_get_changeset: unsafe extern "C" fn() -> *const c_char,
     pub libvlc_free: unsafe extern "C" fn(_: *mut c_void),
     pub libvlc_event_attach: unsafe extern "C" fn(_: *mut libvlc_event_manager_t, _: libvlc_event_type_t, _: libvlc_callback_t, _: *mut c_void) -> c_int,
+    pub libvlc_event_detach: unsafe extern "C" fn(_: *mut libvlc_event_manager_t, _: libvlc_event_type_t, _: libvlc_callback_t, _: *mut c_void),
     pub libvlc_event_type_name: unsafe extern "C" fn(_: libvlc_event_type_t) -> *const c_char,
     pub libvlc_log_get_context: unsafe extern "C" fn(_: *const libvlc_log_t, _: *const *const c_char, _: *const *const c_char, _: *mut c_uint),
     pub libvlc_log_get_object: unsafe extern "C" fn(_: *const libvlc_log_t, _: *const *const c_char, _: *const *const c_char, _: *mut uintptr_t),
@@ -495,6 +555,7 @@ pub struct VlcDll {
     pub libvlc_media_new_location: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_media_t,
     pub libvlc_media_new_path: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_media_t,
     pub libvlc_media_new_fd: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: c_int) -> *mut libvlc_media_t,
+    pub libvlc_media_new_callbacks: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: libvlc_media_open_cb, _: libvlc_media_read_cb, _: libvlc_media_seek_cb, _: libvlc_media_close_cb, _: *mut c_void) -> *mut libvlc_media_t,
     pub libvlc_media_add_option: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *const c_char),
     pub libvlc_media_add_option_flag: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *const c_char, _: c_uint),
     pub libvlc_media_retain: unsafe extern "C" fn(_: *mut libvlc_media_t),
@@ -511,11 +572,20 @@ pub struct VlcDll {
     pub libvlc_media_get_duration: unsafe extern "C" fn(_: *mut libvlc_media_t) -> libvlc_time_t,
     pub libvlc_media_parse: unsafe extern "C" fn(_: *mut libvlc_media_t),
     pub libvlc_media_parse_async: unsafe extern "C" fn(_: *mut libvlc_media_t),
+    pub libvlc_media_parse_with_options: unsafe extern "C" fn(_: *mut libvlc_media_t, _: c_int, _: c_int) -> c_int,
+    pub libvlc_media_parse_stop: unsafe extern "C" fn(_: *mut libvlc_media_t),
     pub libvlc_media_is_parsed: unsafe extern "C" fn(_: *mut libvlc_media_t) -> c_int,
+    pub libvlc_media_get_parsed_status: unsafe extern "C" fn(_: *mut libvlc_media_t) -> c_int,
     pub libvlc_media_set_user_data: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut c_void),
     pub libvlc_media_get_user_data: unsafe extern "C" fn(_: *mut libvlc_media_t) -> *mut c_void,
     pub libvlc_media_tracks_get: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut *mut *mut libvlc_media_track_t) -> c_uint,
     pub libvlc_media_tracks_release: unsafe extern "C" fn(_: *mut *mut libvlc_media_track_t, _: c_uint),
+    pub libvlc_media_get_codec_description: unsafe extern "C" fn(_: libvlc_track_type_t, _: u32) -> *const c_char,
+    pub libvlc_media_get_type: unsafe extern "C" fn(_: *mut libvlc_media_t) -> libvlc_media_type_t,
+    pub libvlc_media_slaves_add: unsafe extern "C" fn(_: *mut libvlc_media_t, _: libvlc_media_slave_type_t, _: c_uint, _: *const c_char) -> c_int,
+    pub libvlc_media_slaves_clear: unsafe extern "C" fn(_: *mut libvlc_media_t),
+    pub libvlc_media_slaves_get: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut *mut *mut libvlc_media_slave_t) -> c_uint,
+    pub libvlc_media_slaves_release: unsafe extern "C" fn(_: *mut *mut libvlc_media_slave_t, _: c_uint),
     pub libvlc_media_player_new: unsafe extern "C" fn(_: *mut libvlc_instance_t) -> *mut libvlc_media_player_t,
     pub libvlc_media_player_new_from_media: unsafe extern "C" fn(_: *mut libvlc_media_t) -> *mut libvlc_media_player_t,
     pub libvlc_media_player_release: unsafe extern "C" fn(_: *mut libvlc_media_player_t),
@@ -556,6 +626,10 @@ pub struct VlcDll {
     pub libvlc_media_player_get_title_count: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_int,
     pub libvlc_media_player_previous_chapter: unsafe extern "C" fn(_: *mut libvlc_media_player_t),
     pub libvlc_media_player_next_chapter: unsafe extern "C" fn(_: *mut libvlc_media_player_t),
+    pub libvlc_media_player_get_full_title_descriptions: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: *mut *mut *mut libvlc_title_description_t) -> c_int,
+    pub libvlc_title_descriptions_release: unsafe extern "C" fn(_: *mut *mut libvlc_title_description_t, _: c_uint),
+    pub libvlc_media_player_get_full_chapter_descriptions: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_int, _: *mut *mut *mut libvlc_chapter_description_t) -> c_int,
+    pub libvlc_chapter_descriptions_release: unsafe extern "C" fn(_: *mut *mut libvlc_chapter_description_t, _: c_uint),
     pub libvlc_media_player_get_rate: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_float,
     pub libvlc_media_player_set_rate: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_float) -> c_int,
     pub libvlc_media_player_get_state: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> libvlc_state_t,
@@ -617,6 +691,7 @@ pub struct VlcDll {
     pub libvlc_audio_output_device_list_get: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_audio_output_device_t,
     pub libvlc_audio_output_device_list_release: unsafe extern "C" fn(_: *mut libvlc_audio_output_device_t),
     pub libvlc_audio_output_device_set: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: *const c_char, _: *const c_char),
+    pub libvlc_audio_output_device_get: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> *mut c_char,
     pub libvlc_audio_toggle_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t),
     pub libvlc_audio_get_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_int,
     pub libvlc_audio_set_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_int),
@@ -710,6 +785,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
         let libvlc_get_changeset: unsafe extern "C" fn() -> *const c_char = transmute(lib.get(b"libvlc_get_changeset")?);
         let libvlc_free: unsafe extern "C" fn(_: *mut c_void) = transmute(lib.get(b"libvlc_free")?);
         let libvlc_event_attach: unsafe extern "C" fn(_: *mut libvlc_event_manager_t, _: libvlc_event_type_t, _: libvlc_callback_t, _: *mut c_void) -> c_int = transmute(lib.get(b"libvlc_event_attach")?);
+        let libvlc_event_detach: unsafe extern "C" fn(_: *mut libvlc_event_manager_t, _: libvlc_event_type_t, _: libvlc_callback_t, _: *mut c_void) = transmute(lib.get(b"libvlc_event_detach")?);
         let libvlc_event_type_name: unsafe extern "C" fn(_: libvlc_event_type_t) -> *const c_char = transmute(lib.get(b"libvlc_event_type_name")?);
         let libvlc_log_get_context: unsafe extern "C" fn(_: *const libvlc_log_t, _: *const *const c_char, _: *const *const c_char, _: *mut c_uint) = transmute(lib.get(b"libvlc_log_get_context")?);
         let libvlc_log_get_object: unsafe extern "C" fn(_: *const libvlc_log_t, _: *const *const c_char, _: *const *const c_char, _: *mut uintptr_t) = transmute(lib.get(b"libvlc_log_get_object")?);
@@ -723,6 +799,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
         let libvlc_media_new_location: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_media_t = transmute(lib.get(b"libvlc_media_new_location")?);
         let libvlc_media_new_path: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_media_t = transmute(lib.get(b"libvlc_media_new_path")?);
         let libvlc_media_new_fd: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: c_int) -> *mut libvlc_media_t = transmute(lib.get(b"libvlc_media_new_fd")?);
+        let libvlc_media_new_callbacks: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: libvlc_media_open_cb, _: libvlc_media_read_cb, _: libvlc_media_seek_cb, _: libvlc_media_close_cb, _: *mut c_void) -> *mut libvlc_media_t = transmute(lib.get(b"libvlc_media_new_callbacks")?);
         let libvlc_media_add_option: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *const c_char) = transmute(lib.get(b"libvlc_media_add_option")?);
         let libvlc_media_add_option_flag: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *const c_char, _: c_uint) = transmute(lib.get(b"libvlc_media_add_option_flag")?);
         let libvlc_media_retain: unsafe extern "C" fn(_: *mut libvlc_media_t) = transmute(lib.get(b"libvlc_media_retain")?);
@@ -739,11 +816,20 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
         let libvlc_media_get_duration: unsafe extern "C" fn(_: *mut libvlc_media_t) -> libvlc_time_t = transmute(lib.get(b"libvlc_media_get_duration")?);
         let libvlc_media_parse: unsafe extern "C" fn(_: *mut libvlc_media_t) = transmute(lib.get(b"libvlc_media_parse")?);
         let libvlc_media_parse_async: unsafe extern "C" fn(_: *mut libvlc_media_t) = transmute(lib.get(b"libvlc_media_parse_async")?);
+        let libvlc_media_parse_with_options: unsafe extern "C" fn(_: *mut libvlc_media_t, _: c_int, _: c_int) -> c_int = transmute(lib.get(b"libvlc_media_parse_with_options")?);
+        let libvlc_media_parse_stop: unsafe extern "C" fn(_: *mut libvlc_media_t) = transmute(lib.get(b"libvlc_media_parse_stop")?);
         let libvlc_media_is_parsed: unsafe extern "C" fn(_: *mut libvlc_media_t) -> c_int = transmute(lib.get(b"libvlc_media_is_parsed")?);
+        let libvlc_media_get_parsed_status: unsafe extern "C" fn(_: *mut libvlc_media_t) -> c_int = transmute(lib.get(b"libvlc_media_get_parsed_status")?);
         let libvlc_media_set_user_data: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut c_void) = transmute(lib.get(b"libvlc_media_set_user_data")?);
         let libvlc_media_get_user_data: unsafe extern "C" fn(_: *mut libvlc_media_t) -> *mut c_void = transmute(lib.get(b"libvlc_media_get_user_data")?);
         let libvlc_media_tracks_get: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut *mut *mut libvlc_media_track_t) -> c_uint = transmute(lib.get(b"libvlc_media_tracks_get")?);
         let libvlc_media_tracks_release: unsafe extern "C" fn(_: *mut *mut libvlc_media_track_t, _: c_uint) = transmute(lib.get(b"libvlc_media_tracks_release")?);
+        let libvlc_media_get_codec_description: unsafe extern "C" fn(_: libvlc_track_type_t, _: u32) -> *const c_char = transmute(lib.get(b"libvlc_media_get_codec_description")?);
+        let libvlc_media_get_type: unsafe extern "C" fn(_: *mut libvlc_media_t) -> libvlc_media_type_t = transmute(lib.get(b"libvlc_media_get_type")?);
+        let libvlc_media_slaves_add: unsafe extern "C" fn(_: *mut libvlc_media_t, _: libvlc_media_slave_type_t, _: c_uint, _: *const c_char) -> c_int = transmute(lib.get(b"libvlc_media_slaves_add")?);
+        let libvlc_media_slaves_clear: unsafe extern "C" fn(_: *mut libvlc_media_t) = transmute(lib.get(b"libvlc_media_slaves_clear")?);
+        let libvlc_media_slaves_get: unsafe extern "C" fn(_: *mut libvlc_media_t, _: *mut *mut *mut libvlc_media_slave_t) -> c_uint = transmute(lib.get(b"libvlc_media_slaves_get")?);
+        let libvlc_media_slaves_release: unsafe extern "C" fn(_: *mut *mut libvlc_media_slave_t, _: c_uint) = transmute(lib.get(b"libvlc_media_slaves_release")?);
         let libvlc_media_player_new: unsafe extern "C" fn(_: *mut libvlc_instance_t) -> *mut libvlc_media_player_t = transmute(lib.get(b"libvlc_media_player_new")?);
         let libvlc_media_player_new_from_media: unsafe extern "C" fn(_: *mut libvlc_media_t) -> *mut libvlc_media_player_t = transmute(lib.get(b"libvlc_media_player_new_from_media")?);
         let libvlc_media_player_release: unsafe extern "C" fn(_: *mut libvlc_media_player_t) = transmute(lib.get(b"libvlc_media_player_release")?);
@@ -784,6 +870,10 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
         let libvlc_media_player_get_title_count: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_int = transmute(lib.get(b"libvlc_media_player_get_title_count")?);
         let libvlc_media_player_previous_chapter: unsafe extern "C" fn(_: *mut libvlc_media_player_t) = transmute(lib.get(b"libvlc_media_player_previous_chapter")?);
         let libvlc_media_player_next_chapter: unsafe extern "C" fn(_: *mut libvlc_media_player_t) = transmute(lib.get(b"libvlc_media_player_next_chapter")?);
+        let libvlc_media_player_get_full_title_descriptions: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: *mut *mut *mut libvlc_title_description_t) -> c_int = transmute(lib.get(b"libvlc_media_player_get_full_title_descriptions")?);
+        let libvlc_title_descriptions_release: unsafe extern "C" fn(_: *mut *mut libvlc_title_description_t, _: c_uint) = transmute(lib.get(b"libvlc_title_descriptions_release")?);
+        let libvlc_media_player_get_full_chapter_descriptions: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_int, _: *mut *mut *mut libvlc_chapter_description_t) -> c_int = transmute(lib.get(b"libvlc_media_player_get_full_chapter_descriptions")?);
+        let libvlc_chapter_descriptions_release: unsafe extern "C" fn(_: *mut *mut libvlc_chapter_description_t, _: c_uint) = transmute(lib.get(b"libvlc_chapter_descriptions_release")?);
         let libvlc_media_player_get_rate: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_float = transmute(lib.get(b"libvlc_media_player_get_rate")?);
         let libvlc_media_player_set_rate: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_float) -> c_int = transmute(lib.get(b"libvlc_media_player_set_rate")?);
         let libvlc_media_player_get_state: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> libvlc_state_t = transmute(lib.get(b"libvlc_media_player_get_state")?);
@@ -845,6 +935,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
         let libvlc_audio_output_device_list_get: unsafe extern "C" fn(_: *mut libvlc_instance_t, _: *const c_char) -> *mut libvlc_audio_output_device_t = transmute(lib.get(b"libvlc_audio_output_device_list_get")?);
         let libvlc_audio_output_device_list_release: unsafe extern "C" fn(_: *mut libvlc_audio_output_device_t) = transmute(lib.get(b"libvlc_audio_output_device_list_release")?);
         let libvlc_audio_output_device_set: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: *const c_char, _: *const c_char) = transmute(lib.get(b"libvlc_audio_output_device_set")?);
+        let libvlc_audio_output_device_get: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> *mut c_char = transmute(lib.get(b"libvlc_audio_output_device_get")?);
         let libvlc_audio_toggle_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t) = transmute(lib.get(b"libvlc_audio_toggle_mute")?);
         let libvlc_audio_get_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t) -> c_int = transmute(lib.get(b"libvlc_audio_get_mute")?);
         let libvlc_audio_set_mute: unsafe extern "C" fn(_: *mut libvlc_media_player_t, _: c_int) = transmute(lib.get(b"libvlc_audio_set_mute")?);
@@ -934,6 +1025,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
             libvlc_get_changeset,
             libvlc_free,
             libvlc_event_attach,
+            libvlc_event_detach,
             libvlc_event_type_name,
             libvlc_log_get_context,
             libvlc_log_get_object,
@@ -947,6 +1039,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
             libvlc_media_new_location,
             libvlc_media_new_path,
             libvlc_media_new_fd,
+            libvlc_media_new_callbacks,
             libvlc_media_add_option,
             libvlc_media_add_option_flag,
             libvlc_media_retain,
@@ -963,11 +1056,20 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
             libvlc_media_get_duration,
             libvlc_media_parse,
             libvlc_media_parse_async,
+            libvlc_media_parse_with_options,
+            libvlc_media_parse_stop,
             libvlc_media_is_parsed,
+            libvlc_media_get_parsed_status,
             libvlc_media_set_user_data,
             libvlc_media_get_user_data,
             libvlc_media_tracks_get,
             libvlc_media_tracks_release,
+            libvlc_media_get_codec_description,
+            libvlc_media_get_type,
+            libvlc_media_slaves_add,
+            libvlc_media_slaves_clear,
+            libvlc_media_slaves_get,
+            libvlc_media_slaves_release,
             libvlc_media_player_new,
             libvlc_media_player_new_from_media,
             libvlc_media_player_release,
@@ -1008,6 +1110,10 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
             libvlc_media_player_get_title_count,
             libvlc_media_player_previous_chapter,
             libvlc_media_player_next_chapter,
+            libvlc_media_player_get_full_title_descriptions,
+            libvlc_title_descriptions_release,
+            libvlc_media_player_get_full_chapter_descriptions,
+            libvlc_chapter_descriptions_release,
             libvlc_media_player_get_rate,
             libvlc_media_player_set_rate,
             libvlc_media_player_get_state,
@@ -1069,6 +1175,7 @@ fn initialize_library(path: &std::path::Path) -> Option<VlcDll> {
             libvlc_audio_output_device_list_get,
             libvlc_audio_output_device_list_release,
             libvlc_audio_output_device_set,
+            libvlc_audio_output_device_get,
             libvlc_audio_toggle_mute,
             libvlc_audio_get_mute,
             libvlc_audio_set_mute,
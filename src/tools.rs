@@ -3,7 +3,7 @@
 // Licensed under the MIT license, see the LICENSE file.
 
 use std::ffi::{CString, CStr, NulError};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::borrow::Cow;
 use libc::c_char;
 
@@ -36,8 +36,34 @@ pub unsafe fn from_cstr_ref<'a>(p: *const c_char) -> Option<Cow<'a, str>> {
 }
 
 // Create CString from &Path
+// On Unix the raw bytes are used, so non-UTF-8 paths are passed losslessly
 pub fn path_to_cstr(path: &Path) -> Result<CString, NulError> {
+    #[cfg(unix)]
+    let path = {
+        use std::os::unix::ffi::OsStrExt;
+        CString::new(path.as_os_str().as_bytes())?
+    };
+    #[cfg(not(unix))]
     let path = CString::new(path.to_string_lossy().into_owned())?;
 
     Ok(path)
 }
+
+// Convert *const c_char to PathBuf
+// On Unix the raw bytes are used, so non-UTF-8 paths are converted losslessly
+pub unsafe fn from_cstr_path(p: *const c_char) -> Option<PathBuf> {
+    if p.is_null() {
+        return None;
+    }
+    let cstr = CStr::from_ptr(p);
+
+    #[cfg(unix)]
+    let path = {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(::std::ffi::OsStr::from_bytes(cstr.to_bytes()))
+    };
+    #[cfg(not(unix))]
+    let path = PathBuf::from(cstr.to_string_lossy().into_owned());
+
+    Some(path)
+}
@@ -5,9 +5,13 @@
 use crate::sys;
 use crate::MediaPlayer;
 use crate::TrackDescription;
-use crate::enums::VideoAdjustOption;
-use crate::tools::{to_cstr, from_cstr};
+use crate::FourCC;
+use crate::enums::{VideoAdjustOption, TeletextKey, TrackType};
+use crate::tools::{to_cstr, from_cstr, path_to_cstr};
 use libc::c_void;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
 
 pub trait MediaPlayerVideoEx {
     fn toggle_fullscreen(&self);
@@ -21,13 +25,45 @@ pub trait MediaPlayerVideoEx {
     fn get_cursor(&self, num: u32) -> Option<(i32, i32)>;
     fn get_scale(&self) -> f32;
     fn set_scale(&self, factor: f32);
-    fn get_aspect_ratio(&self) -> Option<String>;
-    fn set_aspect_ratio(&self, aspect: Option<&str>);
+    fn get_aspect_ratio(&self) -> AspectRatio;
+    fn set_aspect_ratio(&self, aspect: &AspectRatio);
+    /// Returns the current crop, None if the video is not cropped.
+    fn get_crop(&self) -> Option<Crop>;
+    fn set_crop(&self, crop: Option<&Crop>);
+    /// Enable deinterlacing with the given mode, or disable it with None.
+    fn set_deinterlace(&self, mode: Option<DeinterlaceMode>);
     fn get_video_track_description(&self) -> Option<Vec<TrackDescription>>;
     fn get_adjust_int(&self, option: VideoAdjustOption) -> i32;
     fn set_adjust_int(&self, option: VideoAdjustOption, value: i32);
     fn get_adjust_float(&self, option: VideoAdjustOption) -> f32;
     fn set_adjust_float(&self, option: VideoAdjustOption, value: f32);
+    /// Returns the current subtitle track, None if subtitles are disabled.
+    fn get_spu(&self) -> Option<i32>;
+    /// Select the subtitle track by id, see `get_spu_description()`, or disable subtitles with None.
+    fn set_spu(&self, track: Option<i32>) -> Result<(), ()>;
+    fn get_spu_count(&self) -> i32;
+    fn get_spu_description(&self) -> Option<Vec<TrackDescription>>;
+    /// Load an external subtitle file and select it.
+    fn set_subtitle_file(&self, path: &Path) -> Result<(), ()>;
+    /// Subtitle delay in microseconds, positive values delay the subtitles.
+    fn get_spu_delay(&self) -> i64;
+    fn set_spu_delay(&self, delay: i64) -> Result<(), ()>;
+    /// Get the requested teletext page, None if no teletext track is selected.
+    /// libvlc starts every player at page 100, which is returned until another page is set.
+    ///
+    /// Looks up the tracks of the current media on every call (see `teletext_tracks()`),
+    /// avoid calling it in a tight loop.
+    fn teletext_page(&self) -> Option<u16>;
+    /// Display the teletext page `page` (100 to 899, e.g. 888 for subtitles), or hide teletext with 0.
+    /// If no teletext track is selected, the first one is selected.
+    fn set_teletext_page(&self, page: u16) -> Result<(), ()>;
+    /// Send a navigation key to the displayed teletext page.
+    fn press_teletext_key(&self, key: TeletextKey);
+    /// Toggle teletext transparent status on video output.
+    fn toggle_teletext(&self);
+    /// Get the teletext tracks of the current media, a subset of `get_spu_description()`.
+    /// A teletext track can be selected with `set_spu()` like any subtitle track.
+    fn teletext_tracks(&self) -> Vec<TrackDescription>;
 }
 
 impl MediaPlayerVideoEx for MediaPlayer {
@@ -77,38 +113,52 @@ impl MediaPlayerVideoEx for MediaPlayer {
     fn set_video_track(&self, track: i32) {
         unsafe{ (sys::get_vlc_dll().libvlc_video_set_track)(self.ptr, track); }
     }
-    fn get_aspect_ratio(&self) -> Option<String> {
+    fn get_aspect_ratio(&self) -> AspectRatio {
         unsafe{
             let p = (sys::get_vlc_dll().libvlc_video_get_aspect_ratio)(self.ptr);
             let s = from_cstr(p);
             if !p.is_null() { (sys::get_vlc_dll().libvlc_free)(p as *mut c_void); }
-            s
+            s.map_or(AspectRatio::Default, |s| AspectRatio::parse(&s))
         }
     }
-    fn set_aspect_ratio(&self, aspect: Option<&str>) {
+    fn set_aspect_ratio(&self, aspect: &AspectRatio) {
         unsafe{
-            if let Some(a) = aspect {
-                (sys::get_vlc_dll().libvlc_video_set_aspect_ratio)(self.ptr, to_cstr(a).as_ptr());
-            }else{
+            if let AspectRatio::Default = *aspect {
                 (sys::get_vlc_dll().libvlc_video_set_aspect_ratio)(self.ptr, ::std::ptr::null());
+            }else{
+                (sys::get_vlc_dll().libvlc_video_set_aspect_ratio)(self.ptr, to_cstr(&aspect.to_string()).as_ptr());
             }
         }
     }
-    fn get_video_track_description(&self) -> Option<Vec<TrackDescription>> {
+    fn get_crop(&self) -> Option<Crop> {
+        unsafe{
+            let p = (sys::get_vlc_dll().libvlc_video_get_crop_geometry)(self.ptr);
+            let s = from_cstr(p);
+            if !p.is_null() { (sys::get_vlc_dll().libvlc_free)(p as *mut c_void); }
+            s.and_then(|s| s.parse().ok())
+        }
+    }
+    fn set_crop(&self, crop: Option<&Crop>) {
         unsafe{
-            let p0 = (sys::get_vlc_dll().libvlc_video_get_track_description)(self.ptr);
-            if p0.is_null() { return None; }
-            let mut td = Vec::new();
-            let mut p = p0;
-
-            while !(*p).p_next.is_null() {
-                td.push(TrackDescription{ id: (*p).i_id, name: from_cstr((*p).psz_name) });
-                p = (*p).p_next;
+            if let Some(c) = crop {
+                (sys::get_vlc_dll().libvlc_video_set_crop_geometry)(self.ptr, to_cstr(&c.to_string()).as_ptr());
+            }else{
+                (sys::get_vlc_dll().libvlc_video_set_crop_geometry)(self.ptr, ::std::ptr::null());
             }
-            (sys::get_vlc_dll().libvlc_track_description_list_release)(p0);
-            Some(td)
         }
     }
+    fn set_deinterlace(&self, mode: Option<DeinterlaceMode>) {
+        unsafe{
+            if let Some(m) = mode {
+                (sys::get_vlc_dll().libvlc_video_set_deinterlace)(self.ptr, to_cstr(m.name()).as_ptr());
+            }else{
+                (sys::get_vlc_dll().libvlc_video_set_deinterlace)(self.ptr, ::std::ptr::null());
+            }
+        }
+    }
+    fn get_video_track_description(&self) -> Option<Vec<TrackDescription>> {
+        unsafe{ TrackDescription::from_list((sys::get_vlc_dll().libvlc_video_get_track_description)(self.ptr)) }
+    }
     fn get_adjust_int(&self, option: VideoAdjustOption) -> i32 {
         unsafe{ (sys::get_vlc_dll().libvlc_video_get_adjust_int)(self.ptr, option as u32) }
     }
@@ -121,4 +171,246 @@ impl MediaPlayerVideoEx for MediaPlayer {
     fn set_adjust_float(&self, option: VideoAdjustOption, value: f32) {
         unsafe{ (sys::get_vlc_dll().libvlc_video_set_adjust_float)(self.ptr, option as u32, value); }
     }
+    fn get_spu(&self) -> Option<i32> {
+        unsafe{
+            let track = (sys::get_vlc_dll().libvlc_video_get_spu)(self.ptr);
+            if track == -1 { None }else{ Some(track) }
+        }
+    }
+    fn set_spu(&self, track: Option<i32>) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_video_set_spu)(self.ptr, track.unwrap_or(-1)) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn get_spu_count(&self) -> i32 {
+        unsafe{ (sys::get_vlc_dll().libvlc_video_get_spu_count)(self.ptr) }
+    }
+    fn get_spu_description(&self) -> Option<Vec<TrackDescription>> {
+        unsafe{ TrackDescription::from_list((sys::get_vlc_dll().libvlc_video_get_spu_description)(self.ptr)) }
+    }
+    fn set_subtitle_file(&self, path: &Path) -> Result<(), ()> {
+        let path = path_to_cstr(path).map_err(|_| ())?;
+        unsafe{
+            // Returns a boolean, unlike most libvlc functions
+            if (sys::get_vlc_dll().libvlc_video_set_subtitle_file)(self.ptr, path.as_ptr()) != 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn get_spu_delay(&self) -> i64 {
+        unsafe{ (sys::get_vlc_dll().libvlc_video_get_spu_delay)(self.ptr) }
+    }
+    fn set_spu_delay(&self, delay: i64) -> Result<(), ()> {
+        unsafe{
+            if (sys::get_vlc_dll().libvlc_video_set_spu_delay)(self.ptr, delay) == 0 { Ok(()) }else{ Err(()) }
+        }
+    }
+    fn teletext_page(&self) -> Option<u16> {
+        let spu = self.get_spu()?;
+        if !self.teletext_tracks().iter().any(|t| t.id == spu) {
+            return None;
+        }
+        let page = unsafe{ (sys::get_vlc_dll().libvlc_video_get_teletext)(self.ptr) };
+        if page <= 0 { None }else{ Some(page as u16) }
+    }
+    fn set_teletext_page(&self, page: u16) -> Result<(), ()> {
+        if page != 0 && !(100..=899).contains(&page) { return Err(()); }
+        unsafe{ (sys::get_vlc_dll().libvlc_video_set_teletext)(self.ptr, page as i32); }
+        Ok(())
+    }
+    fn press_teletext_key(&self, key: TeletextKey) {
+        unsafe{ (sys::get_vlc_dll().libvlc_video_set_teletext)(self.ptr, key as i32); }
+    }
+    fn toggle_teletext(&self) {
+        unsafe{ (sys::get_vlc_dll().libvlc_toggle_teletext)(self.ptr); }
+    }
+    fn teletext_tracks(&self) -> Vec<TrackDescription> {
+        let telx = FourCC::from_bytes(*b"telx");
+        let ids: Vec<i32> = self.get_media()
+            .and_then(|md| md.tracks())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| t.track_type == TrackType::Text && t.codec == telx)
+            .map(|t| t.id)
+            .collect();
+        if ids.is_empty() {
+            return Vec::new();
+        }
+
+        unsafe{ TrackDescription::from_list((sys::get_vlc_dll().libvlc_video_get_spu_description)(self.ptr)) }
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|td| ids.contains(&td.id))
+            .collect()
+    }
+}
+
+/// Aspect ratio of the video output.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AspectRatio {
+    /// The aspect ratio of the source
+    Default,
+    /// Width and height, e.g. `Ratio(16, 9)`
+    Ratio(u32, u32),
+    /// Any other value libvlc accepts
+    Custom(String),
+}
+
+impl AspectRatio {
+    /// Parse a libvlc aspect ratio string, such as `"16:9"`.
+    pub fn parse(s: &str) -> AspectRatio {
+        if s.is_empty() {
+            return AspectRatio::Default;
+        }
+        match parse_pair(s, ':') {
+            Some((w, h)) if w > 0 && h > 0 => AspectRatio::Ratio(w, h),
+            _ => AspectRatio::Custom(s.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AspectRatio::Default => Ok(()),
+            AspectRatio::Ratio(w, h) => write!(f, "{}:{}", w, h),
+            AspectRatio::Custom(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Crop geometry of the video output.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Crop {
+    /// Crop to an aspect ratio, `"W:H"`
+    Ratio(u32, u32),
+    /// Keep a window of the video, `"WxH+X+Y"`
+    Window { width: u32, height: u32, x: u32, y: u32 },
+    /// Remove borders (in pixels), `"L+T+R+B"`
+    Borders { left: u32, top: u32, right: u32, bottom: u32 },
+}
+
+impl fmt::Display for Crop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Crop::Ratio(w, h) => write!(f, "{}:{}", w, h),
+            Crop::Window{ width, height, x, y } => write!(f, "{}x{}+{}+{}", width, height, x, y),
+            Crop::Borders{ left, top, right, bottom } => write!(f, "{}+{}+{}+{}", left, top, right, bottom),
+        }
+    }
+}
+
+impl FromStr for Crop {
+    type Err = InvalidGeometry;
+
+    fn from_str(s: &str) -> Result<Crop, InvalidGeometry> {
+        if let Some((w, h)) = parse_pair(s, ':') {
+            if w == 0 || h == 0 {
+                return Err(InvalidGeometry{ geometry: s.to_owned() });
+            }
+            return Ok(Crop::Ratio(w, h));
+        }
+
+        let numbers: Option<Vec<u32>> = if let Some(i) = s.find('x') {
+            let (width, rest) = (&s[..i], &s[i + 1..]);
+            ::std::iter::once(width).chain(rest.split('+')).map(|n| n.parse().ok()).collect()
+        }else{
+            s.split('+').map(|n| n.parse().ok()).collect()
+        };
+        // Like libvlc, the offsets of a window are optional
+        match (s.contains('x'), numbers.as_ref().map(|n| &n[..])) {
+            (true, Some(&[width, height])) => Ok(Crop::Window{ width, height, x: 0, y: 0 }),
+            (true, Some(&[width, height, x])) => Ok(Crop::Window{ width, height, x, y: 0 }),
+            (true, Some(&[width, height, x, y])) => Ok(Crop::Window{ width, height, x, y }),
+            (false, Some(&[left, top, right, bottom])) => Ok(Crop::Borders{ left, top, right, bottom }),
+            _ => Err(InvalidGeometry{ geometry: s.to_owned() }),
+        }
+    }
+}
+
+/// Error returned when a geometry string can not be parsed.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidGeometry {
+    pub geometry: String,
+}
+
+impl fmt::Display for InvalidGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid geometry \"{}\"", self.geometry)
+    }
+}
+
+impl ::std::error::Error for InvalidGeometry {}
+
+fn parse_pair(s: &str, sep: char) -> Option<(u32, u32)> {
+    let i = s.find(sep)?;
+    Some((s[..i].parse().ok()?, s[i + 1..].parse().ok()?))
+}
+
+/// Deinterlacing algorithms of VLC 3.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DeinterlaceMode {
+    Blend,
+    Bob,
+    Linear,
+    X,
+    Yadif,
+    Yadif2x,
+    Phosphor,
+    /// Inverse telecine
+    Ivtc,
+}
+
+impl DeinterlaceMode {
+    /// Name of the mode, as expected by libvlc.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            DeinterlaceMode::Blend => "blend",
+            DeinterlaceMode::Bob => "bob",
+            DeinterlaceMode::Linear => "linear",
+            DeinterlaceMode::X => "x",
+            DeinterlaceMode::Yadif => "yadif",
+            DeinterlaceMode::Yadif2x => "yadif2x",
+            DeinterlaceMode::Phosphor => "phosphor",
+            DeinterlaceMode::Ivtc => "ivtc",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_ratio_round_trip() {
+        assert_eq!(AspectRatio::parse(""), AspectRatio::Default);
+        assert_eq!(AspectRatio::parse("16:9"), AspectRatio::Ratio(16, 9));
+        assert_eq!(AspectRatio::parse("2.35:1"), AspectRatio::Custom("2.35:1".to_owned()));
+        assert_eq!(AspectRatio::parse("0:1"), AspectRatio::Custom("0:1".to_owned()));
+        for s in &["", "16:9", "4:3", "2.35:1"] {
+            assert_eq!(&AspectRatio::parse(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn crop_round_trip() {
+        assert_eq!("16:9".parse(), Ok(Crop::Ratio(16, 9)));
+        assert_eq!("100x50+10+20".parse(), Ok(Crop::Window{ width: 100, height: 50, x: 10, y: 20 }));
+        assert_eq!("10+20+30+40".parse(), Ok(Crop::Borders{ left: 10, top: 20, right: 30, bottom: 40 }));
+        for s in &["16:9", "100x50+10+20", "10+20+30+40"] {
+            assert_eq!(&s.parse::<Crop>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn crop_window_offsets_are_optional() {
+        assert_eq!("100x50".parse(), Ok(Crop::Window{ width: 100, height: 50, x: 0, y: 0 }));
+        assert_eq!("100x50+10".parse(), Ok(Crop::Window{ width: 100, height: 50, x: 10, y: 0 }));
+    }
+
+    #[test]
+    fn crop_rejects_malformed() {
+        for s in &["", "16", "0:9", "16:0", "16:9:1", "x50+1+2", "100x", "100x50+1+2+3",
+                   "1+2+3", "1+2+3+4+5", "-1+2+3+4", "a+b+c+d", "16 : 9"] {
+            assert_eq!(s.parse::<Crop>(), Err(InvalidGeometry{ geometry: s.to_string() }), "{:?}", s);
+        }
+    }
 }
@@ -0,0 +1,291 @@
+// Copyright (c) 2015 T. Okubo
+// This file is part of vlc-rs.
+// Licensed under the MIT license, see the LICENSE file.
+
+use crate::sys;
+use crate::FourCC;
+use libc::{c_void, c_char, c_uint};
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of planes libvlc passes to the video callbacks (PICTURE_PLANE_MAX)
+const MAX_PLANES: usize = 5;
+
+/// Number of picture buffers allocated per video output
+const POOL_SIZE: usize = 3;
+
+/// Alignment of the plane buffers
+const ALIGN: usize = 32;
+
+/// Format of the decoded video, passed to `VideoSink::format()`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct VideoFormat {
+    pub chroma: FourCC,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Memory layout of one plane of a picture.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PlaneLayout {
+    /// Bytes per line
+    pub pitch: u32,
+    /// Number of lines
+    pub lines: u32,
+}
+
+/// Format and memory layout of the pictures delivered to a `VideoSink`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BufferLayout {
+    /// Chroma libvlc converts the video to, e.g. `RV32` or `I420`
+    pub chroma: FourCC,
+    pub width: u32,
+    pub height: u32,
+    /// Up to 5 planes
+    pub planes: Vec<PlaneLayout>,
+}
+
+// Round up to a multiple of `to`, 0 if the result does not fit in u32.
+// A pitch of 0 makes the layout invalid, so the video output refuses it.
+fn align(value: Option<u32>, to: u32) -> u32 {
+    value.and_then(|v| v.div_ceil(to).checked_mul(to)).unwrap_or(0)
+}
+
+impl BufferLayout {
+    /// Layout for a single-plane (packed) chroma such as `RV32`, `RV24` or `RGBA`.
+    /// If a line does not fit in 4 GiB, the layout is refused by the video output.
+    pub fn packed(chroma: FourCC, width: u32, height: u32, bytes_per_pixel: u32) -> BufferLayout {
+        BufferLayout {
+            chroma, width, height,
+            planes: vec![PlaneLayout{ pitch: align(width.checked_mul(bytes_per_pixel), 32), lines: height }],
+        }
+    }
+
+    /// 32-bit RGB, 8 bits per component (B, G, R, unused in memory on little-endian).
+    pub fn rv32(width: u32, height: u32) -> BufferLayout {
+        BufferLayout::packed(FourCC::from_bytes(*b"RV32"), width, height, 4)
+    }
+
+    /// Planar YUV 4:2:0, one luma and two chroma planes.
+    pub fn i420(width: u32, height: u32) -> BufferLayout {
+        let lines = align(Some(height), 16);
+        let chroma_pitch = align(Some(width.div_ceil(2)), 32);
+        BufferLayout {
+            chroma: FourCC::from_bytes(*b"I420"), width, height,
+            planes: vec![
+                PlaneLayout{ pitch: align(Some(width), 32), lines },
+                PlaneLayout{ pitch: chroma_pitch, lines: lines / 2 },
+                PlaneLayout{ pitch: chroma_pitch, lines: lines / 2 },
+            ],
+        }
+    }
+}
+
+/// One plane of a `Frame`.
+#[derive(Clone, Copy, Debug)]
+pub struct Plane<'a> {
+    pub data: &'a [u8],
+    pub pitch: u32,
+    pub lines: u32,
+}
+
+/// A decoded picture, only valid during `VideoSink::display()`.
+#[derive(Clone, Debug)]
+pub struct Frame<'a> {
+    pub chroma: FourCC,
+    pub width: u32,
+    pub height: u32,
+    /// `sys::libvlc_clock()` (in µs) when libvlc asked for the picture to be displayed.
+    /// This is not the picture PTS, which the video callbacks do not expose.
+    pub display_time: i64,
+    pub planes: Vec<Plane<'a>>,
+}
+
+/// Receives the decoded video of a `MediaPlayer`, see `MediaPlayer::set_video_sink()`.
+///
+/// All methods are called from libvlc threads.
+pub trait VideoSink: Send + 'static {
+    /// Choose the layout of the pictures for a video of the given source format,
+    /// libvlc converts the video if necessary. Return None to refuse the video.
+    /// Called every time a video output is started, e.g. when the format changes.
+    fn format(&mut self, source: &VideoFormat) -> Option<BufferLayout>;
+
+    /// A picture is ready to be displayed, libvlc calls this when the picture is due.
+    /// Copy the planes if they are needed after returning.
+    ///
+    /// The video callbacks of libvlc 3 do not expose the picture PTS, only the time
+    /// the picture is displayed at is known (`Frame::display_time`).
+    fn display(&mut self, frame: &Frame<'_>);
+
+    /// The video output was closed, e.g. because playback stopped.
+    fn cleanup(&mut self) {}
+}
+
+pub(crate) type SharedVideoSink = Arc<Mutex<Box<dyn VideoSink>>>;
+
+// Player level slot, owned by the MediaPlayer and passed as opaque pointer to libvlc.
+// Each video output takes its own reference to the sink in the format callback.
+pub(crate) type VideoSinkSlot = Mutex<Option<SharedVideoSink>>;
+
+struct PictureBuffer {
+    // Only accessed through `base`, never through a reference, as libvlc writes into it
+    _data: Vec<u8>,
+    base: *mut u8,
+    // (offset, len) of each plane from `base`, offsets are aligned to ALIGN
+    planes: Vec<(usize, usize)>,
+}
+
+// The layout has 1 to MAX_PLANES non-empty planes, and the buffer size fits in usize
+fn valid_layout(layout: &BufferLayout) -> bool {
+    if layout.planes.is_empty() || layout.planes.len() > MAX_PLANES {
+        return false;
+    }
+    layout.planes.iter().try_fold(ALIGN, |size, plane| {
+        if plane.pitch == 0 || plane.lines == 0 {
+            return None;
+        }
+        let len = (plane.pitch as usize).checked_mul(plane.lines as usize)?;
+        size.checked_add(len.checked_next_multiple_of(ALIGN)?)
+    }).is_some()
+}
+
+impl PictureBuffer {
+    // `layout` must be valid, see valid_layout()
+    fn new(layout: &BufferLayout) -> PictureBuffer {
+        let mut planes = Vec::with_capacity(layout.planes.len());
+        let mut size = 0;
+        for plane in &layout.planes {
+            let len = plane.pitch as usize * plane.lines as usize;
+            planes.push((size, len));
+            size += len.div_ceil(ALIGN) * ALIGN;
+        }
+        let mut data = vec![0u8; size + ALIGN];
+        let shift = data.as_mut_ptr().align_offset(ALIGN).min(ALIGN);
+        let base = unsafe{ data.as_mut_ptr().add(shift) };
+        PictureBuffer { _data: data, base, planes }
+    }
+}
+
+// State of one video output, created in the format callback and freed in the cleanup callback.
+// lock, unlock and display may be called from different threads (e.g. the decoder and the
+// video output thread), so the state is only ever accessed through a shared reference.
+struct VideoOutput {
+    sink: SharedVideoSink,
+    layout: BufferLayout,
+    pool: Vec<PictureBuffer>,
+    // Indices in `pool` of the buffers libvlc does not hold, taken by lock and given back by unlock
+    free: Mutex<Vec<usize>>,
+    // Written when the pool is unexpectedly exhausted, never displayed
+    scratch: PictureBuffer,
+}
+
+pub(crate) unsafe extern "C" fn video_sink_format(
+    opaque: *mut *mut c_void, chroma: *mut c_char,
+    width: *mut c_uint, height: *mut c_uint,
+    pitches: *mut c_uint, lines: *mut c_uint) -> c_uint
+{
+    let slot = &*(*opaque as *const VideoSinkSlot);
+    let sink = match slot.lock().ok().and_then(|s| s.clone()) {
+        Some(s) => s,
+        None => { return 0; },
+    };
+
+    let mut source_chroma = [0u8; 4];
+    for (i, c) in source_chroma.iter_mut().enumerate() {
+        *c = *chroma.add(i) as u8;
+    }
+    let source = VideoFormat{ chroma: FourCC::from_bytes(source_chroma), width: *width, height: *height };
+
+    let layout = match sink.lock() {
+        Ok(mut s) => s.format(&source),
+        Err(_) => None,
+    };
+    let layout = match layout {
+        Some(l) if valid_layout(&l) => l,
+        _ => { return 0; },
+    };
+
+    for (i, c) in layout.chroma.to_bytes().iter().enumerate() {
+        *chroma.add(i) = *c as c_char;
+    }
+    *width = layout.width;
+    *height = layout.height;
+    for (i, plane) in layout.planes.iter().enumerate() {
+        *pitches.add(i) = plane.pitch;
+        *lines.add(i) = plane.lines;
+    }
+
+    let pool = (0..POOL_SIZE).map(|_| PictureBuffer::new(&layout)).collect();
+    let scratch = PictureBuffer::new(&layout);
+    let free = Mutex::new((0..POOL_SIZE).rev().collect());
+    let output = Box::new(VideoOutput{ sink, layout, pool, free, scratch });
+    *opaque = Box::into_raw(output) as *mut c_void;
+
+    POOL_SIZE as c_uint
+}
+
+pub(crate) unsafe extern "C" fn video_sink_cleanup(opaque: *mut c_void) {
+    let output = Box::from_raw(opaque as *mut VideoOutput);
+    if let Ok(mut sink) = output.sink.lock() {
+        sink.cleanup();
+    };
+}
+
+// Picture identifiers passed to unlock and display, must not be null
+const SCRATCH_PICTURE: usize = usize::MAX;
+
+fn picture_id(index: usize) -> *mut c_void {
+    if index == SCRATCH_PICTURE { index as *mut c_void }else{ (index + 1) as *mut c_void }
+}
+
+fn picture_index(picture: *mut c_void) -> usize {
+    let id = picture as usize;
+    if id == SCRATCH_PICTURE { id }else{ id.wrapping_sub(1) }
+}
+
+// Called when libvlc takes a picture from its pool, which has POOL_SIZE pictures
+pub(crate) unsafe extern "C" fn video_sink_lock(opaque: *mut c_void, planes: *mut *mut c_void) -> *mut c_void {
+    let output = &*(opaque as *const VideoOutput);
+    let index = output.free.lock().ok().and_then(|mut free| free.pop()).unwrap_or(SCRATCH_PICTURE);
+
+    let buffer = output.pool.get(index).unwrap_or(&output.scratch);
+    for (i, (offset, _)) in buffer.planes.iter().enumerate() {
+        *planes.add(i) = buffer.base.add(*offset) as *mut c_void;
+    }
+    picture_id(index)
+}
+
+// Called when libvlc gives the picture back to its pool, after it was displayed (if it was)
+pub(crate) unsafe extern "C" fn video_sink_unlock(opaque: *mut c_void, picture: *mut c_void, _planes: *const *mut c_void) {
+    let output = &*(opaque as *const VideoOutput);
+    let index = picture_index(picture);
+    if index < output.pool.len() {
+        if let Ok(mut free) = output.free.lock() {
+            free.push(index);
+        }
+    }
+}
+
+pub(crate) unsafe extern "C" fn video_sink_display(opaque: *mut c_void, picture: *mut c_void) {
+    let output = &*(opaque as *const VideoOutput);
+    // The buffer is held by libvlc until unlock, so it is not handed out again meanwhile
+    let buffer = match output.pool.get(picture_index(picture)) {
+        Some(b) => b,
+        None => { return; },
+    };
+
+    let frame = Frame {
+        chroma: output.layout.chroma,
+        width: output.layout.width,
+        height: output.layout.height,
+        display_time: (sys::get_vlc_dll().libvlc_clock)(),
+        planes: buffer.planes.iter().zip(output.layout.planes.iter()).map(|((offset, len), layout)| Plane {
+            data: ::std::slice::from_raw_parts(buffer.base.add(*offset), *len),
+            pitch: layout.pitch,
+            lines: layout.lines,
+        }).collect(),
+    };
+
+    if let Ok(mut sink) = output.sink.lock() {
+        sink.display(&frame);
+    }
+}